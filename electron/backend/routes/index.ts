@@ -1,7 +1,7 @@
 import { Router } from 'express';
 import { healthCheck } from './health';
 import { getModels, setModel } from './models';
-import { transcribeAudio } from './transcription';
+import { transcribeAudio, cancelTranscription } from './transcription';
 import { getOptions } from './options';
 import { asyncHandler } from '../utils/errors';
 
@@ -16,6 +16,7 @@ router.post('/v1/settings/model', asyncHandler(setModel));
 
 // Transcription endpoint
 router.post('/v1/audio/transcriptions', asyncHandler(transcribeAudio));
+router.delete('/v1/audio/transcriptions/:taskId', asyncHandler(cancelTranscription));
 
 // Options endpoint
 router.get('/v1/options', asyncHandler(getOptions));
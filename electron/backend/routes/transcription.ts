@@ -1,4 +1,5 @@
 import { Request, Response } from 'express';
+import { randomUUID } from 'crypto';
 import multer from 'multer';
 import { getDockerService } from '../services/dockerService';
 import { getAudioService } from '../services/audioService';
@@ -7,9 +8,15 @@ import {
   ServiceUnavailableError, 
   ValidationError, 
   AudioError,
-  NotFoundError 
+  NotFoundError,
+  TranscriptionCancelledError
 } from '../utils/errors';
 
+const TASK_ID_PATTERN = /^[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}$/i;
+
+// Running transcriptions by task id, so DELETE can abort them
+const activeTranscriptions = new Map<string, AbortController>();
+
 // Configure multer for file uploads
 const upload = multer({
   storage: multer.memoryStorage(),
@@ -47,6 +54,7 @@ export interface TranscriptionResponse {
   model_id?: string;
   backend?: string;
   processing_time?: number;
+  task_id?: string;
 }
 
 // Middleware to handle file upload
@@ -71,6 +79,16 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
 
       try {
         const { model = 'whisper-base', response_format = 'json' } = req.query;
+        // Clients may pick the task id up front so they can cancel before the
+        // response arrives; otherwise one is generated and sent back.
+        const requestedTaskId = req.query.task_id as string | undefined;
+        if (requestedTaskId && !TASK_ID_PATTERN.test(requestedTaskId)) {
+          throw new ValidationError('task_id must be a UUID');
+        }
+        const taskId = requestedTaskId || randomUUID();
+        if (activeTranscriptions.has(taskId)) {
+          throw new ValidationError(`Transcription ${taskId} is already running`);
+        }
         const filename = req.file.originalname;
         const fileBuffer = req.file.buffer;
 
@@ -105,13 +123,14 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
         // Send transcription start notification via WebSocket
         const io = (req as any).io;
         if (io) {
-          io.emit('transcription_started', { filename, model });
+          io.emit('transcription_started', { task_id: taskId, filename, model });
         }
 
         // Progress callback for WebSocket updates
         const progressCallback = (progress: number, status: string) => {
           if (io) {
             io.emit('transcription_progress', {
+              task_id: taskId,
               filename,
               progress: Math.round(progress * 100),
               status
@@ -119,18 +138,24 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
           }
         };
 
+        const controller = new AbortController();
+        activeTranscriptions.set(taskId, controller);
+        res.setHeader('X-Task-Id', taskId);
+
         try {
           // Transcribe the audio file
-          logger.info(`Starting transcription of ${filename} with model ${model}`);
+          logger.info(`Starting transcription ${taskId} of ${filename} with model ${model}`);
           
           const result = await audioService.transcribeAudio(
             cleanedBuffer,
             {
               model: model as string,
-              responseFormat: response_format as 'json' | 'text' | 'srt'
+              responseFormat: response_format as 'json' | 'text' | 'srt',
+              signal: controller.signal
             },
             progressCallback
           );
+          result.task_id = taskId;
 
           // Ensure result has Docker-specific fields
           if (!result.model_id) {
@@ -157,6 +182,7 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
           // Send transcription complete notification
           if (io) {
             io.emit('transcription_completed', {
+              task_id: taskId,
               filename,
               result_length: result.text?.length || 0,
               model,
@@ -186,12 +212,21 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
           }
 
         } catch (transcriptionError) {
+          if (transcriptionError instanceof TranscriptionCancelledError) {
+            logger.info(`Transcription ${taskId} of ${filename} was cancelled`);
+            if (io) {
+              io.emit('transcription_cancelled', { task_id: taskId, filename, model });
+            }
+            throw transcriptionError;
+          }
+
           // Send transcription error notification
           const errorMsg = transcriptionError instanceof Error ? transcriptionError.message : 'Unknown error';
           logger.error(`Transcription failed for ${filename}: ${errorMsg}`, transcriptionError);
 
           if (io) {
             io.emit('transcription_error', {
+              task_id: taskId,
               filename,
               error: errorMsg,
               model
@@ -199,6 +234,8 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
           }
 
           throw transcriptionError;
+        } finally {
+          activeTranscriptions.delete(taskId);
         }
 
       } catch (error) {
@@ -210,4 +247,18 @@ export const transcribeAudio = async (req: Request, res: Response): Promise<void
     logger.error('Failed to transcribe audio:', error);
     throw error;
   }
-};
\ No newline at end of file
+};
+
+// Aborts a running transcription started with the given task id
+export const cancelTranscription = async (req: Request, res: Response): Promise<void> => {
+  const { taskId } = req.params;
+  const controller = activeTranscriptions.get(taskId);
+  if (!controller) {
+    throw new NotFoundError(`No running transcription with task id ${taskId}`);
+  }
+
+  logger.info(`Cancelling transcription ${taskId}`);
+  controller.abort();
+  activeTranscriptions.delete(taskId);
+  res.json({ task_id: taskId, status: 'cancelled' });
+};
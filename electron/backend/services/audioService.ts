@@ -3,7 +3,7 @@ import path from 'path';
 import fs from 'fs';
 import { EventEmitter } from 'events';
 import { logger } from '../utils/logger';
-import { AudioError } from '../utils/errors';
+import { AudioError, TranscriptionCancelledError } from '../utils/errors';
 
 export interface AudioConversionOptions {
   targetSampleRate?: number;
@@ -26,6 +26,7 @@ export interface TranscriptionOptions {
   responseFormat?: 'json' | 'text' | 'srt';
   temperature?: number;
   bestOf?: number;
+  signal?: AbortSignal;
 }
 
 export interface TranscriptionResult {
@@ -48,8 +49,15 @@ export interface TranscriptionResult {
   model_id?: string;
   backend?: string;
   processing_time?: number;
+  task_id?: string;
 }
 
+const throwIfCancelled = (signal?: AbortSignal): void => {
+  if (signal?.aborted) {
+    throw new TranscriptionCancelledError();
+  }
+};
+
 class AudioService extends EventEmitter {
   private supportedAudioFormats = ['.wav', '.mp3', '.m4a', '.flac', '.ogg', '.aac', '.wma', '.aiff', '.opus'];
   private supportedVideoFormats = ['.mp4', '.avi', '.mkv', '.mov', '.flv', '.webm', '.3gp', '.wmv', '.mpg', '.mpeg', '.m4v', '.ts', '.f4v'];
@@ -328,7 +336,8 @@ class AudioService extends EventEmitter {
         language,
         responseFormat = 'json',
         temperature = 0.0,
-        bestOf = 1
+        bestOf = 1,
+        signal
       } = options;
 
       if (progressCallback) {
@@ -341,6 +350,8 @@ class AudioService extends EventEmitter {
         targetChannels: 1
       });
 
+      throwIfCancelled(signal);
+
       if (progressCallback) {
         progressCallback(0.3, 'Connecting to transcription service...');
       }
@@ -362,6 +373,8 @@ class AudioService extends EventEmitter {
         }
       }
 
+      throwIfCancelled(signal);
+
       if (progressCallback) {
         progressCallback(0.5, 'Sending audio to transcription service...');
       }
@@ -377,7 +390,7 @@ class AudioService extends EventEmitter {
           responseFormat,
           temperature,
           bestOf
-        }, progressCallback);
+        }, progressCallback, signal);
 
         if (progressCallback) {
           progressCallback(0.9, 'Processing transcription result...');
@@ -402,6 +415,10 @@ class AudioService extends EventEmitter {
         }
       }
     } catch (error) {
+      if (error instanceof TranscriptionCancelledError) {
+        logger.info('Transcription cancelled');
+        throw error;
+      }
       logger.error('Transcription failed:', error);
       throw new AudioError(`Transcription failed: ${error.message}`);
     }
@@ -411,7 +428,8 @@ class AudioService extends EventEmitter {
     container: any,
     audioFile: string,
     options: any,
-    progressCallback?: (progress: number, status: string) => void
+    progressCallback?: (progress: number, status: string) => void,
+    signal?: AbortSignal
   ): Promise<TranscriptionResult> {
     // This is a placeholder implementation
     // In a real implementation, you would send the audio file to the container
@@ -422,7 +440,13 @@ class AudioService extends EventEmitter {
     }
 
     // Simulate processing time
-    await new Promise(resolve => setTimeout(resolve, 2000));
+    await new Promise<void>((resolve, reject) => {
+      const timer = setTimeout(resolve, 2000);
+      signal?.addEventListener('abort', () => {
+        clearTimeout(timer);
+        reject(new TranscriptionCancelledError());
+      }, { once: true });
+    });
 
     if (progressCallback) {
       progressCallback(0.8, 'Generating transcription...');
@@ -60,6 +60,13 @@ export class NotFoundError extends ASRProError {
   }
 }
 
+export class TranscriptionCancelledError extends ASRProError {
+  constructor(message: string = 'Transcription cancelled', details?: any) {
+    super(message, 'TRANSCRIPTION_CANCELLED', 409, details);
+    this.name = 'TranscriptionCancelledError';
+  }
+}
+
 export class ServiceUnavailableError extends ASRProError {
   constructor(message: string, details?: any) {
     super(message, 'SERVICE_UNAVAILABLE', 503, details);
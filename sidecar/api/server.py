@@ -20,6 +20,7 @@ import logging
 import asyncio
 import json
 import io
+import uuid
 import email.parser
 from email.message import EmailMessage
 
@@ -45,6 +46,9 @@ logger = logging.getLogger(__name__)
 # Global model manager instance
 model_manager = None
 
+# Running transcriptions by task id, so they can be cancelled
+active_transcriptions: dict[str, asyncio.Task] = {}
+
 
 class ConnectionManager:
     """WebSocket connection manager."""
@@ -278,6 +282,7 @@ def create_app(settings: Settings) -> FastAPI:
         request: Request,
         model: Optional[str] = "whisper-base",
         response_format: str = "json",
+        task_id: Optional[str] = None,
     ):
         """
         Transcribe audio file using AI speech recognition.
@@ -299,8 +304,21 @@ def create_app(settings: Settings) -> FastAPI:
         - Real-time progress updates via WebSocket
         - CUDA acceleration when available
         - Automatic model loading if not active
+        - Pass `task_id` (a UUID) to cancel the request later via DELETE
         """
         try:
+            if task_id is None:
+                task_id = str(uuid.uuid4())
+            else:
+                try:
+                    task_id = str(uuid.UUID(task_id))
+                except ValueError:
+                    raise HTTPException(status_code=400, detail="task_id must be a UUID")
+            if task_id in active_transcriptions:
+                raise HTTPException(
+                    status_code=409, detail=f"Transcription {task_id} is already running"
+                )
+
             # Get raw request data and parse multipart manually
             content_type_header = request.headers.get("content-type", "")
             logger.info(f"Request content-type header: {content_type_header}")
@@ -463,25 +481,39 @@ def create_app(settings: Settings) -> FastAPI:
             
             # Send transcription start notification
             await send_websocket_message(
-                "transcription_started", {"filename": filename, "model": model}
+                "transcription_started",
+                {"task_id": task_id, "filename": filename, "model": model},
             )
 
             # Progress callback for WebSocket updates
             async def progress_callback(progress: float, status: str):
                 await send_websocket_message(
                     "transcription_progress",
-                    {"filename": filename, "progress": int(progress * 100), "status": status},
+                    {
+                        "task_id": task_id,
+                        "filename": filename,
+                        "progress": int(progress * 100),
+                        "status": status,
+                    },
                 )
 
             try:
                 # Transcribe the audio file
-                logger.info(f"Starting transcription of {filename} with model {model}")
-                result = await model_manager.transcribe_data(
-                    audio_data=file_content,
-                    model_id=model,
-                    progress_callback=progress_callback,
-                    response_format=response_format
+                logger.info(f"Starting transcription {task_id} of {filename} with model {model}")
+                task = asyncio.create_task(
+                    model_manager.transcribe_data(
+                        audio_data=file_content,
+                        model_id=model,
+                        progress_callback=progress_callback,
+                        response_format=response_format
+                    )
                 )
+                active_transcriptions[task_id] = task
+                try:
+                    result = await task
+                finally:
+                    active_transcriptions.pop(task_id, None)
+                result["task_id"] = task_id
 
                 # Ensure result has Docker-specific fields
                 if "model_id" not in result:
@@ -505,6 +537,7 @@ def create_app(settings: Settings) -> FastAPI:
                 await send_websocket_message(
                     "transcription_completed",
                     {
+                        "task_id": task_id,
                         "filename": filename,
                         "result_length": len(result.get("text", "")),
                         "model": model,
@@ -516,7 +549,10 @@ def create_app(settings: Settings) -> FastAPI:
                 
                 # Return the transcription result
                 return result
-                
+
+            except asyncio.CancelledError:
+                logger.info(f"Transcription {task_id} of {filename} was cancelled")
+                raise HTTPException(status_code=409, detail="Transcription cancelled")
             except Exception as e:
                 # Send transcription error notification
                 error_msg = str(e)
@@ -525,6 +561,7 @@ def create_app(settings: Settings) -> FastAPI:
                 await send_websocket_message(
                     "transcription_error",
                     {
+                        "task_id": task_id,
                         "filename": filename,
                         "error": error_msg,
                         "model": model,
@@ -557,6 +594,24 @@ def create_app(settings: Settings) -> FastAPI:
             logger.error(f"Failed to transcribe audio: {e}", exc_info=True)
             raise HTTPException(status_code=500, detail="Failed to transcribe audio")
 
+    @app.delete("/v1/audio/transcriptions/{task_id}", tags=["Transcription"])
+    async def cancel_transcription(task_id: str):
+        """
+        Cancel a running transcription.
+
+        Use the `task_id` passed to (or returned by) the transcription request.
+        """
+        task = active_transcriptions.pop(task_id, None)
+        if task is None:
+            raise HTTPException(
+                status_code=404, detail=f"No running transcription with task id {task_id}"
+            )
+
+        logger.info(f"Cancelling transcription {task_id}")
+        task.cancel()
+        await send_websocket_message("transcription_cancelled", {"task_id": task_id})
+        return {"task_id": task_id, "status": "cancelled"}
+
     @app.get("/v1/options", tags=["Configuration"])
     async def get_options():
         """
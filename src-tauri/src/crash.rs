@@ -0,0 +1,610 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, TryLockError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const PENDING_EXTENSION: &str = "crash";
+const SEEN_EXTENSION: &str = "txt";
+const SECRET_KEYS: [&str; 4] = ["api_key", "apikey", "token", "password"];
+/// Absolute paths that belong to the Rust toolchain, not to the user.
+const TOOLCHAIN_PREFIXES: [&[u8]; 1] = [b"/rustc/"];
+const REDACTED: &[u8] = b"[REDACTED]";
+const PATH_PLACEHOLDER: &[u8] = b"[PATH]";
+const MAX_LOG_LINES: usize = 200;
+/// Longer lines are redacted in chunks of this size.
+const LINE_CAPACITY: usize = 1024;
+/// Bytes held back from each chunk of a long line, so a key or path cut off
+/// at the end of one chunk is seen whole in the next.
+const CHUNK_OVERLAP: usize = 64;
+
+static LOG_LINES: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// Keeps `line` among the last `MAX_LOG_LINES` lines attached to crash reports.
+pub fn remember_log_line(line: String) {
+    let mut lines = LOG_LINES.lock().unwrap_or_else(|e| e.into_inner());
+    if lines.len() == MAX_LOG_LINES {
+        lines.pop_front();
+    }
+    lines.push_back(line);
+}
+
+/// Installs a panic hook that writes a crash report into `crash_dir` before
+/// handing over to the default hook. The contents of the `settings` files are
+/// included, redacted like the rest of the report.
+pub fn install_panic_hook(crash_dir: PathBuf, settings: Vec<PathBuf>) {
+    let _ = fs::create_dir_all(&crash_dir);
+    let home = home_dir();
+    // Sized for the longest possible report name, so naming a report inside
+    // the hook never has to grow the path.
+    let longest_name = format!("crash-{}-{}.{}", u64::MAX, u32::MAX, PENDING_EXTENSION);
+    let report_path = Mutex::new(crash_dir.join(longest_name));
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Best effort only: every write below ignores its error so the
+        // report writer can never turn a panic into a double panic.
+        write_report(&report_path, home.as_deref(), &settings, info);
+        default_hook(info);
+    }));
+}
+
+/// Reports written by a previous run that the user has not been told about yet.
+pub fn pending_reports(crash_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(crash_dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == PENDING_EXTENSION))
+        .collect();
+    reports.sort();
+    reports
+}
+
+/// Renames pending reports to plain text files so they are only announced
+/// once, returning where each report now lives.
+pub fn mark_reports_seen(reports: &[PathBuf]) -> Vec<PathBuf> {
+    reports
+        .iter()
+        .map(|report| {
+            let seen = report.with_extension(SEEN_EXTENSION);
+            match fs::rename(report, &seen) {
+                Ok(()) => seen,
+                Err(_) => report.clone(),
+            }
+        })
+        .collect()
+}
+
+/// What the panic hook knows about a crash.
+struct CrashDetails<'a> {
+    timestamp: u64,
+    thread: &'a str,
+    location: &'a dyn fmt::Display,
+    message: &'a str,
+    backtrace: &'a dyn fmt::Display,
+}
+
+/// Captures the backtrace only when it is written, so everything before it is
+/// already on disk if capturing fails.
+struct CapturedBacktrace;
+
+impl fmt::Display for CapturedBacktrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&std::backtrace::Backtrace::force_capture(), f)
+    }
+}
+
+/// Streams the report straight into the file through a fixed-size redaction
+/// buffer; apart from the backtrace nothing here allocates.
+fn write_report(
+    report_path: &Mutex<PathBuf>,
+    home: Option<&str>,
+    settings: &[PathBuf],
+    info: &PanicHookInfo,
+) {
+    // A second panic racing this one just goes without a report.
+    let Ok(mut path) = report_path.try_lock() else {
+        return;
+    };
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let timestamp = now.as_secs();
+    // The nanoseconds keep two panics in the same second from sharing a name.
+    let mut name = [0u8; 64];
+    let mut cursor = io::Cursor::new(&mut name[..]);
+    if write!(cursor, "crash-{}-{:09}.{}", timestamp, now.subsec_nanos(), PENDING_EXTENSION).is_err() {
+        return;
+    }
+    let len = cursor.position() as usize;
+    let Ok(name) = std::str::from_utf8(&name[..len]) else {
+        return;
+    };
+    path.set_file_name(name);
+    let Ok(file) = fs::OpenOptions::new().write(true).create_new(true).open(&*path) else {
+        return;
+    };
+
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("<non-string panic payload>");
+    let thread = std::thread::current();
+    let location: &dyn fmt::Display = match info.location() {
+        Some(location) => location,
+        None => &"<unknown>",
+    };
+    let details = CrashDetails {
+        timestamp,
+        thread: thread.name().unwrap_or("<unnamed>"),
+        location,
+        message,
+        backtrace: &CapturedBacktrace,
+    };
+
+    // The panic may have happened while logging, with the lock held.
+    let logs = match LOG_LINES.try_lock() {
+        Ok(lines) => Some(lines),
+        Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    };
+    let log_lines = logs.iter().flat_map(|lines| lines.iter()).map(String::as_str);
+
+    let mut out = RedactingWriter::new(file, home);
+    let _ = build_report(&mut out, &details, settings, log_lines);
+    let _ = out.finish().and_then(|mut file| file.flush());
+}
+
+fn build_report<'a, W: Write>(
+    out: &mut W,
+    details: &CrashDetails<'_>,
+    settings: &[PathBuf],
+    log_lines: impl Iterator<Item = &'a str>,
+) -> io::Result<()> {
+    write!(
+        out,
+        "ASR Pro crash report\n\
+         version: {}\n\
+         os: {} ({})\n\
+         time: {}\n\
+         thread: {}\n\
+         location: {}\n\
+         message: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        details.timestamp,
+        details.thread,
+        details.location,
+        details.message,
+    )?;
+
+    for path in settings {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("settings");
+        write!(out, "\nsettings ({}):\n", name)?;
+        match fs::File::open(path) {
+            Ok(mut file) => {
+                io::copy(&mut file, out)?;
+                out.write_all(b"\n")?;
+            }
+            Err(_) => out.write_all(b"(not set)\n")?,
+        }
+    }
+
+    out.write_all(b"\nlog:\n")?;
+    for line in log_lines {
+        out.write_all(line.as_bytes())?;
+        out.write_all(b"\n")?;
+    }
+
+    write!(out, "\nbacktrace:\n{}\n", details.backtrace)
+}
+
+/// How an earlier chunk of a long line left off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Carry {
+    #[default]
+    Nothing,
+    /// The chunk ended inside a replaced path; drop bytes until it ends.
+    InPath,
+    /// A secret's value started; drop the rest of the line.
+    Secret,
+}
+
+/// Redacts everything written through it line by line, using a fixed buffer.
+struct RedactingWriter<'a, W: Write> {
+    inner: W,
+    home: Option<&'a [u8]>,
+    line: [u8; LINE_CAPACITY],
+    len: usize,
+    carry: Carry,
+}
+
+impl<'a, W: Write> RedactingWriter<'a, W> {
+    fn new(inner: W, home: Option<&'a str>) -> Self {
+        Self {
+            inner,
+            home: home.filter(|h| !h.is_empty()).map(str::as_bytes),
+            line: [0; LINE_CAPACITY],
+            len: 0,
+            carry: Carry::Nothing,
+        }
+    }
+
+    fn finish(mut self) -> io::Result<W> {
+        self.write_line()?;
+        Ok(self.inner)
+    }
+
+    fn write_line(&mut self) -> io::Result<()> {
+        let len = std::mem::take(&mut self.len);
+        match std::mem::take(&mut self.carry) {
+            Carry::Secret => Ok(()),
+            carry => redact_line(&mut self.inner, &self.line[..len], self.home, carry == Carry::InPath),
+        }
+    }
+
+    /// Writes out the front of a full buffer, keeping the last bytes (from a
+    /// token boundary where possible) to be redacted with what follows.
+    fn write_chunk(&mut self) -> io::Result<()> {
+        let in_path = match self.carry {
+            Carry::Secret => {
+                self.len = 0;
+                return Ok(());
+            }
+            carry => carry == Carry::InPath,
+        };
+        let line = &self.line[..self.len];
+        if secret_value_start(line).is_some() {
+            redact_line(&mut self.inner, line, self.home, in_path)?;
+            self.carry = Carry::Secret;
+            self.len = 0;
+            return Ok(());
+        }
+
+        let last_split = self.len - CHUNK_OVERLAP;
+        let split = line[..=last_split]
+            .iter()
+            .rposition(|b| is_path_end(*b))
+            .filter(|i| *i > 0)
+            .unwrap_or(last_split);
+        let ended_in_path = write_paths(&mut self.inner, &line[..split], self.home, in_path)?;
+        self.carry = if ended_in_path { Carry::InPath } else { Carry::Nothing };
+        self.line.copy_within(split..self.len, 0);
+        self.len -= split;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for RedactingWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        for &byte in buf {
+            if byte == b'\n' {
+                self.write_line()?;
+                self.inner.write_all(b"\n")?;
+                continue;
+            }
+            if self.len == LINE_CAPACITY {
+                self.write_chunk()?;
+            }
+            self.line[self.len] = byte;
+            self.len += 1;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_line()?;
+        self.inner.flush()
+    }
+}
+
+/// Writes `line` with the value after a secret key (`api_key=...`,
+/// `"token": "..."`, `Bearer ...`) masked, the home directory shortened to
+/// `~` and any other absolute path replaced. With `in_path` the line
+/// continues a replaced path, so its start is dropped up to the path's end.
+fn redact_line<W: Write>(
+    out: &mut W,
+    line: &[u8],
+    home: Option<&[u8]>,
+    in_path: bool,
+) -> io::Result<()> {
+    match secret_value_start(line) {
+        Some(start) => {
+            write_paths(out, &line[..start], home, in_path)?;
+            out.write_all(REDACTED)
+        }
+        None => write_paths(out, line, home, in_path).map(|_| ()),
+    }
+}
+
+fn secret_value_start(line: &[u8]) -> Option<usize> {
+    if let Some(idx) = find_ignore_case(line, b"bearer ", 0) {
+        return Some(idx + b"bearer ".len());
+    }
+
+    for key in SECRET_KEYS {
+        let mut from = 0;
+        while let Some(idx) = find_ignore_case(line, key.as_bytes(), from) {
+            let after_key = idx + key.len();
+            let quotes = line[after_key..]
+                .iter()
+                .take_while(|b| matches!(b, b'"' | b'\'' | b' '))
+                .count();
+            if matches!(line.get(after_key + quotes), Some(b':' | b'=')) {
+                let value = after_key + quotes + 1;
+                let spaces = line[value..].iter().take_while(|b| **b == b' ').count();
+                return Some(value + spaces);
+            }
+            from = after_key;
+        }
+    }
+    None
+}
+
+fn find_ignore_case(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle))
+        .map(|idx| idx + from)
+}
+
+/// Returns whether `text` ended inside a replaced path.
+fn write_paths<W: Write>(
+    out: &mut W,
+    text: &[u8],
+    home: Option<&[u8]>,
+    in_path: bool,
+) -> io::Result<bool> {
+    let mut i = 0;
+    if in_path {
+        match text.iter().position(|b| is_path_end(*b)) {
+            Some(end) => i = end,
+            None => return Ok(true),
+        }
+    }
+    let mut written = i;
+    let mut ended_in_path = false;
+    while i < text.len() {
+        if let Some(home) = home.filter(|h| text[i..].starts_with(h)) {
+            out.write_all(&text[written..i])?;
+            out.write_all(b"~")?;
+            i += home.len();
+            written = i;
+        } else if is_path_start(text, i) {
+            let end = text[i..]
+                .iter()
+                .position(|b| is_path_end(*b))
+                .map_or(text.len(), |len| i + len);
+            out.write_all(&text[written..i])?;
+            let path = &text[i..end];
+            if TOOLCHAIN_PREFIXES.iter().any(|prefix| path.starts_with(prefix)) {
+                out.write_all(path)?;
+            } else {
+                out.write_all(PATH_PLACEHOLDER)?;
+                ended_in_path = end == text.len();
+            }
+            i = end;
+            written = end;
+        } else {
+            i += 1;
+        }
+    }
+    out.write_all(&text[written..])?;
+    Ok(ended_in_path)
+}
+
+/// An absolute Unix (`/...`) or Windows (`C:\...`) path starting a token.
+fn is_path_start(text: &[u8], i: usize) -> bool {
+    let at_boundary = i == 0
+        || matches!(
+            text[i - 1],
+            b' ' | b'\t' | b'"' | b'\'' | b'(' | b'[' | b'<' | b'=' | b','
+        );
+    if !at_boundary {
+        return false;
+    }
+    match text[i..] {
+        [b'/', next, ..] => !is_path_end(next),
+        [drive, b':', b'\\' | b'/', ..] => drive.is_ascii_alphabetic(),
+        _ => false,
+    }
+}
+
+fn is_path_end(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || matches!(byte, b'"' | b'\'' | b')' | b']' | b'>' | b',')
+}
+
+fn home_dir() -> Option<String> {
+    std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redacted(line: &str, home: Option<&str>) -> String {
+        let mut out = Vec::new();
+        redact_line(&mut out, line.as_bytes(), home.map(str::as_bytes), false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn masks_bearer_tokens() {
+        assert_eq!(
+            redacted("Authorization: Bearer sk-abc123", None),
+            "Authorization: Bearer [REDACTED]"
+        );
+    }
+
+    #[test]
+    fn masks_api_key_assignments() {
+        assert_eq!(redacted("url?api_key=abc123&x=1", None), "url?api_key=[REDACTED]");
+        assert_eq!(redacted("APIKEY = abc", None), "APIKEY = [REDACTED]");
+    }
+
+    #[test]
+    fn masks_quoted_json_values() {
+        assert_eq!(redacted(r#"  "token": "abc123","#, None), r#"  "token": [REDACTED]"#);
+        assert_eq!(redacted(r#"{"password":"hunter2"}"#, None), r#"{"password":[REDACTED]"#);
+    }
+
+    #[test]
+    fn leaves_keys_without_values_alone() {
+        let line = "at tokenizer::encode (src/tokenizer.rs:12)";
+        assert_eq!(redacted(line, None), line);
+    }
+
+    #[test]
+    fn looks_past_key_lookalikes_for_a_real_key() {
+        assert_eq!(redacted("tokenizer token=abc", None), "tokenizer token=[REDACTED]");
+    }
+
+    #[test]
+    fn leaves_plain_lines_alone() {
+        let line = "thread 'main' panicked: index out of bounds";
+        assert_eq!(redacted(line, Some("/home/alice")), line);
+    }
+
+    #[test]
+    fn shortens_home_paths() {
+        assert_eq!(
+            redacted("opening /home/alice/Music/talk.mp3", Some("/home/alice")),
+            "opening ~/Music/talk.mp3"
+        );
+        assert_eq!(
+            redacted("file:///home/alice/a.wav", Some("/home/alice")),
+            "file://~/a.wav"
+        );
+    }
+
+    #[test]
+    fn replaces_paths_outside_home() {
+        assert_eq!(
+            redacted("opening /mnt/usb/lecture.mp3 failed", Some("/home/alice")),
+            "opening [PATH] failed"
+        );
+        assert_eq!(
+            redacted(r#"path="D:\Recordings\call.wav""#, Some("/home/alice")),
+            r#"path="[PATH]""#
+        );
+    }
+
+    #[test]
+    fn keeps_toolchain_paths_and_urls() {
+        let line = "at /rustc/abc/library/std/src/panicking.rs:12 via http://localhost:3001/v1";
+        assert_eq!(redacted(line, Some("/home/alice")), line);
+    }
+
+    #[test]
+    fn redacts_paths_before_a_secret() {
+        assert_eq!(
+            redacted("/srv/app/config token=abc", Some("/home/alice")),
+            "[PATH] token=[REDACTED]"
+        );
+    }
+
+    #[test]
+    fn redacting_writer_handles_lines_split_across_writes() {
+        let mut out = RedactingWriter::new(Vec::new(), Some("/home/alice"));
+        out.write_all(b"first /home/al").unwrap();
+        out.write_all(b"ice/x\napi_").unwrap();
+        out.write_all(b"key=secret").unwrap();
+        let out = out.finish().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "first ~/x\napi_key=[REDACTED]");
+    }
+
+    fn written_through(text: &str, home: Option<&str>) -> String {
+        let mut out = RedactingWriter::new(Vec::new(), home);
+        out.write_all(text.as_bytes()).unwrap();
+        String::from_utf8(out.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn masks_secrets_across_chunk_boundaries() {
+        for prefix_len in [1000, 1010, 1018, 1020, 1023, 1030] {
+            let line = format!("{}\"api_key\": \"sk-LEAKED-SECRET\" trailing", "x".repeat(prefix_len));
+            let out = written_through(&line, None);
+            assert!(!out.contains("LEAKED"), "leaked with a {}-byte prefix", prefix_len);
+            assert!(out.ends_with("\"api_key\": [REDACTED]"), "prefix {}", prefix_len);
+        }
+    }
+
+    #[test]
+    fn masks_the_rest_of_a_long_line_after_a_secret() {
+        let line = format!("token={} tail\nnext", "s".repeat(3000));
+        assert_eq!(written_through(&line, None), "token=[REDACTED]\nnext");
+    }
+
+    #[test]
+    fn replaces_paths_across_chunk_boundaries() {
+        for prefix_len in [1000, 1015, 1022] {
+            let line = format!("{} /mnt/usb/private/lecture.mp3 done", "x".repeat(prefix_len));
+            let out = written_through(&line, Some("/home/alice"));
+            assert!(!out.contains("private"), "leaked with a {}-byte prefix", prefix_len);
+            assert!(out.ends_with(" [PATH] done"), "prefix {}", prefix_len);
+        }
+    }
+
+    #[test]
+    fn long_lines_without_secrets_pass_through() {
+        let line = "word ".repeat(700);
+        assert_eq!(written_through(&line, None), line);
+    }
+
+    #[test]
+    fn builds_a_redacted_report_from_synthetic_inputs() {
+        let dir = std::env::temp_dir().join(format!("asrpro-crash-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let settings = dir.join("shortcut.json");
+        fs::write(&settings, "{\n  \"accelerator\": \"Ctrl+Space\",\n  \"token\": \"abc\"\n}").unwrap();
+        let missing = dir.join("tray_behavior.json");
+
+        let details = CrashDetails {
+            timestamp: 1_700_000_000,
+            thread: "main",
+            location: &"src/lib.rs:42:5",
+            message: "failed to open /home/alice/secret.wav",
+            backtrace: &"   0: asrpro_lib::run\n             at /home/alice/asrpro/src/lib.rs:42",
+        };
+        let logs = ["Starting ASR Pro application...", "Authorization: Bearer xyz"];
+
+        let mut out = RedactingWriter::new(Vec::new(), Some("/home/alice"));
+        build_report(&mut out, &details, &[settings, missing], logs.into_iter()).unwrap();
+        let report = String::from_utf8(out.finish().unwrap()).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(report.starts_with("ASR Pro crash report\n"));
+        assert!(report.contains(&format!("version: {}\n", env!("CARGO_PKG_VERSION"))));
+        assert!(report.contains(&format!("os: {} ({})\n", std::env::consts::OS, std::env::consts::ARCH)));
+        assert!(report.contains("time: 1700000000\n"));
+        assert!(report.contains("thread: main\n"));
+        assert!(report.contains("location: src/lib.rs:42:5\n"));
+        assert!(report.contains("message: failed to open ~/secret.wav\n"));
+        assert!(report.contains("settings (shortcut.json):\n{\n  \"accelerator\": \"Ctrl+Space\",\n  \"token\": [REDACTED]\n}\n"));
+        assert!(report.contains("settings (tray_behavior.json):\n(not set)\n"));
+        assert!(report.contains("log:\nStarting ASR Pro application...\nAuthorization: Bearer [REDACTED]\n"));
+        assert!(report.contains("backtrace:\n   0: asrpro_lib::run\n             at ~/asrpro/src/lib.rs:42\n"));
+        assert!(!report.contains("alice"));
+    }
+
+    #[test]
+    fn remembers_only_the_most_recent_log_lines() {
+        for i in 0..MAX_LOG_LINES + 5 {
+            remember_log_line(format!("line {}", i));
+        }
+        let lines = LOG_LINES.lock().unwrap();
+        assert_eq!(lines.len(), MAX_LOG_LINES);
+        assert!(lines.iter().any(|line| line == "line 204"));
+        assert!(!lines.iter().any(|line| line == "line 4"));
+    }
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri::{AppHandle, Emitter, Manager, WebviewWindow, WebviewWindowBuilder};
+
+/// Extensions the transcription backend accepts as audio.
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "mp3", "m4a", "flac", "ogg", "aac", "wma", "aiff", "opus"];
+
+/// Picks the audio files out of a launch's arguments, resolving relative paths
+/// against the directory the launch happened in. The program name and flags
+/// such as `--minimized` are skipped, as is anything that isn't an existing
+/// regular file with a supported extension. Paths come back canonicalized.
+pub fn file_args(argv: &[String], cwd: &Path) -> Vec<PathBuf> {
+    argv.iter()
+        .skip(1)
+        .filter(|arg| !arg.is_empty() && !arg.starts_with('-'))
+        .filter_map(|arg| cwd.join(arg).canonicalize().ok())
+        .filter(|path| path.is_file() && is_audio_file(path))
+        .collect()
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUDIO_EXTENSIONS.iter().any(|a| ext.eq_ignore_ascii_case(a)))
+}
+
+/// Files passed to ASR Pro on the command line. Files arriving before the
+/// webview listens for `open-files` (first launch, or while the main window is
+/// recreated) wait here; every forwarded file may be read back once.
+#[derive(Default)]
+pub struct PendingFiles {
+    waiting: Mutex<Vec<PathBuf>>,
+    readable: Mutex<HashSet<PathBuf>>,
+}
+
+impl PendingFiles {
+    pub fn push(&self, files: Vec<PathBuf>) {
+        self.allow(&files);
+        self.waiting.lock().unwrap_or_else(|e| e.into_inner()).extend(files);
+    }
+
+    pub fn take(&self) -> Vec<PathBuf> {
+        std::mem::take(&mut *self.waiting.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Lets the webview read `files`, which it was sent directly.
+    pub fn allow(&self, files: &[PathBuf]) {
+        self.readable
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .extend(files.iter().cloned());
+    }
+
+    /// Reads a forwarded file for transcription. Other paths are refused, so
+    /// the webview can't use this to read arbitrary files.
+    pub fn read(&self, path: &Path) -> Result<Vec<u8>, String> {
+        if !self.readable.lock().unwrap_or_else(|e| e.into_inner()).remove(path) {
+            return Err(format!("{} was not opened with ASR Pro", path.display()));
+        }
+        fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+    }
+}
+
+/// Called in the first instance when ASR Pro is launched again. Brings the
+/// main window back and hands it the files the second launch was given.
+pub fn handle_second_launch(app: &AppHandle, argv: Vec<String>, cwd: String) {
+    log!("Second launch detected, forwarding {} argument(s)", argv.len().saturating_sub(1));
+    let files = file_args(&argv, Path::new(&cwd));
+
+    let Some(window) = app.get_webview_window("main") else {
+        // The recreated webview has no listeners yet; it picks the files up on load.
+        app.state::<PendingFiles>().push(files);
+        match recreate_main_window(app) {
+            Ok(window) => {
+                let _ = window.show();
+                let _ = window.set_focus();
+            }
+            Err(e) => log!("Failed to recreate main window: {}", e),
+        }
+        return;
+    };
+
+    let _ = window.unminimize();
+    let _ = window.show();
+    let _ = window.set_focus();
+
+    if !files.is_empty() {
+        app.state::<PendingFiles>().allow(&files);
+        if let Err(e) = app.emit("open-files", serde_json::json!({ "paths": files })) {
+            log!("Failed to forward files to the main window: {}", e);
+        }
+    }
+}
+
+/// Rebuilds the main window from tauri.conf.json after it has been closed for
+/// good while the app kept running in the tray.
+fn recreate_main_window(app: &AppHandle) -> tauri::Result<WebviewWindow> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == "main")
+        .cloned()
+        .unwrap_or_default();
+    WebviewWindowBuilder::from_config(app, &config)?.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_only_existing_audio_files() {
+        let dir = std::env::temp_dir().join(format!("asrpro-instance-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("folder.mp3")).unwrap();
+        fs::write(dir.join("voice.wav"), b"").unwrap();
+        fs::write(dir.join("LOUD.MP3"), b"").unwrap();
+        fs::write(dir.join("notes.txt"), b"").unwrap();
+        let dir = dir.canonicalize().unwrap();
+
+        let argv: Vec<String> = [
+            "asrpro",
+            "--minimized",
+            "voice.wav",
+            "./LOUD.MP3",
+            "notes.txt",
+            "folder.mp3",
+            "missing.flac",
+            "",
+        ]
+        .iter()
+        .map(|arg| arg.to_string())
+        .collect();
+
+        assert_eq!(
+            file_args(&argv, &dir),
+            vec![dir.join("voice.wav"), dir.join("LOUD.MP3")]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn absolute_paths_ignore_the_launch_directory() {
+        let dir = std::env::temp_dir().join(format!("asrpro-instance-abs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("clip.ogg"), b"").unwrap();
+        let file = dir.canonicalize().unwrap().join("clip.ogg");
+
+        let argv = vec!["asrpro".to_string(), file.display().to_string()];
+        assert_eq!(file_args(&argv, Path::new("/nonexistent")), vec![file]);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn forwarded_files_can_be_read_once() {
+        let dir = std::env::temp_dir().join(format!("asrpro-instance-read-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("clip.wav"), b"RIFF").unwrap();
+        fs::write(dir.join("direct.mp3"), b"ID3").unwrap();
+        fs::write(dir.join("other.wav"), b"RIFF").unwrap();
+
+        let pending = PendingFiles::default();
+        pending.push(vec![dir.join("clip.wav")]);
+        pending.allow(&[dir.join("direct.mp3")]);
+        assert_eq!(pending.take(), vec![dir.join("clip.wav")]);
+        assert!(pending.take().is_empty());
+
+        assert_eq!(pending.read(&dir.join("clip.wav")).unwrap(), b"RIFF");
+        assert_eq!(pending.read(&dir.join("direct.mp3")).unwrap(), b"ID3");
+        assert!(pending.read(&dir.join("clip.wav")).is_err());
+        assert!(pending.read(&dir.join("other.wav")).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
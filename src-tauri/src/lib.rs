@@ -1,8 +1,52 @@
-use tauri::{AppHandle, Emitter, Manager};
-use tauri::menu::{Menu, MenuItemBuilder, PredefinedMenuItem};
-use tauri::tray::{MouseButton, TrayIcon, TrayIconBuilder, TrayIconEvent};
-use tauri_plugin_global_shortcut::{Shortcut, ShortcutEvent, ShortcutState};
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+use tauri::menu::{Menu, MenuItemBuilder, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri_plugin_dialog::{DialogExt, MessageDialogButtons, MessageDialogKind, MessageDialogResult};
+use tauri_plugin_autostart::{MacosLauncher, ManagerExt};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+use tauri_plugin_opener::OpenerExt;
 use serde_json;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Prints a line to stdout and keeps it for the next crash report.
+macro_rules! log {
+    ($($arg:tt)*) => {{
+        let line = format!($($arg)*);
+        println!("{}", line);
+        $crate::crash::remember_log_line(line);
+    }};
+}
+
+mod crash;
+mod instance;
+mod mic_permission;
+mod notifications;
+mod paste;
+mod recent_transcripts;
+mod recording;
+mod settings_file;
+mod shortcut;
+mod transcription;
+mod tray_behavior;
+mod tray_status;
+
+use instance::PendingFiles;
+use mic_permission::{MicPermission, MicStatus, SystemMicCapability};
+use notifications::{NotificationSettings, NotificationState};
+use recent_transcripts::{RecentTranscript, RecentTranscriptAction, RecentTranscriptsState};
+use recording::{RecordingPhase, RecordingState};
+use shortcut::{ShortcutMode, ShortcutSettings};
+use transcription::ActiveTranscriptions;
+use tray_behavior::{TrayAction, TrayBehavior, TrayBehaviorState, TrayClick};
+use tray_status::RecordingIndicator;
+
+const TRAY_ID: &str = "main";
+/// Passed by the login item so ASR Pro starts in the tray without its window.
+const MINIMIZED_FLAG: &str = "--minimized";
 
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -28,7 +72,7 @@ async fn hide_window(app: AppHandle) -> Result<(), String> {
 
 #[tauri::command]
 async fn quit_app(app: AppHandle) -> Result<(), String> {
-    println!("quit_app command received");
+    log!("quit_app command received");
     app.exit(0);
     Ok(())
 }
@@ -39,124 +83,664 @@ async fn show_tray_notification(
     title: String,
     message: String,
     notification_type: String,
+    id: Option<String>,
+    progress: Option<f64>,
 ) -> Result<(), String> {
-    app.emit("show-notification", serde_json::json!({
-        "title": title,
-        "message": message,
-        "type": notification_type
-    })).map_err(|e| e.to_string())?;
+    notifications::notify(&app, &title, &message, &notification_type, id.as_deref(), progress)
+}
 
+#[tauri::command]
+async fn set_do_not_disturb(state: State<'_, NotificationState>, enabled: bool) -> Result<(), String> {
+    state.do_not_disturb.store(enabled, Ordering::Relaxed);
     Ok(())
 }
 
+#[tauri::command]
+async fn get_notification_settings(state: State<'_, NotificationState>) -> Result<NotificationSettings, String> {
+    Ok(*state.lock())
+}
+
+#[tauri::command]
+async fn set_notification_fallback(state: State<'_, NotificationState>, enabled: bool) -> Result<(), String> {
+    state.set_webview_fallback(enabled)
+}
+
+/// Adds a finished transcription to the tray's recent list. The webview passes
+/// the `text` it got back from the backend, which is saved under the app data
+/// dir; callers that already saved it there pass `text_path` instead.
+#[tauri::command]
+async fn record_completed_transcript(
+    app: AppHandle,
+    state: State<'_, RecentTranscriptsState>,
+    id: String,
+    title: String,
+    text: Option<String>,
+    text_path: Option<String>,
+) -> Result<(), String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?.join("transcripts");
+    let text_path = match (text_path, text) {
+        (Some(path), _) => recent_transcripts::resolve_text_path(&dir, Path::new(&path))?,
+        (None, Some(text)) => recent_transcripts::save_text(&dir, &id, &text)?,
+        (None, None) => return Err("Either text or text_path is required".to_string()),
+    };
+    let preview = recent_transcripts::read_preview(&text_path)?;
+    let evicted = state.update(|list| {
+        list.record(RecentTranscript {
+            id,
+            title,
+            text_path,
+            preview,
+            completed_at: chrono::Local::now().timestamp(),
+        })
+    })?;
+    recent_transcripts::remove_saved_texts(&dir, &evicted);
+    refresh_tray_menu(&app).map_err(|e| e.to_string())
+}
+
+/// Whether recording may start; with `recheck` the cached result is dropped
+/// and the system probed again (the banner's "Try again").
+#[tauri::command]
+async fn get_microphone_status(
+    state: State<'_, MicPermission>,
+    recheck: Option<bool>,
+) -> Result<MicStatus, String> {
+    Ok(if recheck.unwrap_or(false) {
+        state.recheck(&SystemMicCapability)
+    } else {
+        state.status(&SystemMicCapability)
+    })
+}
+
+#[tauri::command]
+async fn set_microphone_consent(state: State<'_, MicPermission>, allowed: bool) -> Result<MicStatus, String> {
+    state.set_consent(allowed)?;
+    Ok(state.status(&SystemMicCapability))
+}
+
+/// The webview reports whether its capture attempt got the microphone.
+#[tauri::command]
+async fn report_microphone_access(state: State<'_, MicPermission>, granted: bool) -> Result<MicStatus, String> {
+    state.report_capture(granted);
+    Ok(state.status(&SystemMicCapability))
+}
+
+/// Opens the OS microphone privacy settings where there is a standard page.
+#[tauri::command]
+async fn open_microphone_settings(app: AppHandle) -> Result<(), String> {
+    let url = if cfg!(target_os = "macos") {
+        "x-apple.systempreferences:com.apple.preference.security?Privacy_Microphone"
+    } else if cfg!(target_os = "windows") {
+        "ms-settings:privacy-microphone"
+    } else {
+        return Err("No standard microphone settings page on this platform".to_string());
+    };
+    app.opener().open_url(url, None::<&str>).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn set_recent_transcript_action(
+    state: State<'_, RecentTranscriptsState>,
+    action: RecentTranscriptAction,
+) -> Result<(), String> {
+    state.update(|list| list.action = action)
+}
+
+#[tauri::command]
+async fn get_tray_behavior(state: State<'_, TrayBehaviorState>) -> Result<TrayBehavior, String> {
+    Ok(*state.lock())
+}
+
+#[tauri::command]
+async fn set_tray_behavior(
+    state: State<'_, TrayBehaviorState>,
+    behavior: TrayBehavior,
+) -> Result<(), String> {
+    state.set(behavior)
+}
+
+#[tauri::command]
+async fn transcription_started(
+    app: AppHandle,
+    state: State<'_, ActiveTranscriptions>,
+    task_id: String,
+) -> Result<(), String> {
+    state.start(transcription::parse_task_id(&task_id)?);
+    refresh_tray_menu(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn transcription_finished(
+    app: AppHandle,
+    state: State<'_, ActiveTranscriptions>,
+    task_id: String,
+) -> Result<(), String> {
+    state.finish(&transcription::parse_task_id(&task_id)?);
+    refresh_tray_menu(&app).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn cancel_transcription(app: AppHandle, task_id: String) -> Result<(), String> {
+    let task_id = transcription::parse_task_id(&task_id)?;
+    cancel_transcription_task(&app, task_id).await
+}
+
+async fn cancel_transcription_task(app: &AppHandle, task_id: Uuid) -> Result<(), String> {
+    let backend_result = transcription::cancel_on_backend(&task_id).await;
+    if let Err(e) = &backend_result {
+        log!("Backend could not cancel transcription {}: {}", task_id, e);
+    }
+    // The webview aborts its own request either way, so the task never lingers.
+    app.emit("transcription-cancel", serde_json::json!({ "task_id": task_id.to_string() }))
+        .map_err(|e| e.to_string())?;
+    app.state::<ActiveTranscriptions>().finish(&task_id);
+    refresh_tray_menu(app).map_err(|e| e.to_string())?;
+    backend_result
+}
+
+#[tauri::command]
+async fn set_shortcut_mode(
+    state: State<'_, ShortcutSettings>,
+    mode: ShortcutMode,
+) -> Result<(), String> {
+    state.set_mode(mode)
+}
+
+#[tauri::command]
+async fn set_global_shortcut(
+    app: AppHandle,
+    state: State<'_, ShortcutSettings>,
+    accelerator: String,
+) -> Result<(), String> {
+    let new_shortcut = shortcut::parse_accelerator(&accelerator)?;
+    let old_shortcut = state.registered();
+    let global_shortcut = app.global_shortcut();
+
+    if let Some(old_shortcut) = old_shortcut {
+        if old_shortcut == new_shortcut {
+            return state.set_accelerator(accelerator.trim().to_string());
+        }
+        if global_shortcut.is_registered(old_shortcut) {
+            global_shortcut.unregister(old_shortcut).map_err(|e| e.to_string())?;
+        }
+    }
+
+    if let Err(e) = global_shortcut.register(new_shortcut) {
+        // Put the previous shortcut back so the user isn't left without one.
+        if let Some(old_shortcut) = old_shortcut {
+            let _ = global_shortcut.register(old_shortcut);
+        }
+        return Err(format!("Failed to register shortcut '{}': {}", accelerator, e));
+    }
+
+    state.set_registered(Some(new_shortcut));
+    state.set_accelerator(accelerator.trim().to_string())
+}
+
+fn register_saved_shortcut(app: &AppHandle) {
+    let register = |accelerator: &str| {
+        let shortcut = shortcut::parse_accelerator(accelerator)?;
+        app.global_shortcut().register(shortcut).map_err(|e| e.to_string())?;
+        Ok::<_, String>(shortcut)
+    };
+
+    let state = app.state::<ShortcutSettings>();
+    let accelerator = state.lock().accelerator.clone();
+    let registered = register(&accelerator).or_else(|e| {
+        log!(
+            "Failed to register saved shortcut '{}': {}; falling back to {}",
+            accelerator, e, shortcut::DEFAULT_ACCELERATOR
+        );
+        register(shortcut::DEFAULT_ACCELERATOR)
+    });
+    match registered {
+        Ok(shortcut) => state.set_registered(Some(shortcut)),
+        Err(e) => log!("Failed to register default shortcut: {}", e),
+    }
+}
+
 #[tauri::command]
 async fn start_recording(app: AppHandle) -> Result<(), String> {
-    app.emit("recording-start", {}).map_err(|e| e.to_string())?;
-    Ok(())
+    recording::start(&app, None)
 }
 
+/// Stops recording; with `cancelled` the webview discards the audio. The
+/// overlay calls this too, so Rust's state always matches what it shows.
 #[tauri::command]
-async fn stop_recording(app: AppHandle) -> Result<(), String> {
-    app.emit("recording-stop", {}).map_err(|e| e.to_string())?;
-    Ok(())
+async fn stop_recording(app: AppHandle, cancelled: Option<bool>) -> Result<(), String> {
+    if cancelled.unwrap_or(false) {
+        recording::cancel(&app)
+    } else {
+        recording::stop(&app)
+    }
+}
+
+/// Puts `text` on the clipboard and, with `auto_paste`, pastes it into the
+/// app that was focused before ASR Pro.
+#[tauri::command]
+async fn copy_to_clipboard(app: AppHandle, text: String, auto_paste: Option<bool>) -> Result<(), String> {
+    app.clipboard().write_text(text.clone()).map_err(|e| e.to_string())?;
+
+    let auto_paste = auto_paste.unwrap_or(false);
+    if auto_paste {
+        let handle = app.clone();
+        tauri::async_runtime::spawn_blocking(move || paste::paste_into_previous_app(&handle))
+            .await
+            .map_err(|e| e.to_string())??;
+    }
+
+    app.emit(
+        "clipboard-copied",
+        serde_json::json!({ "length": text.chars().count(), "autoPasted": auto_paste }),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Files passed on the command line before the webview could listen for
+/// `open-files`. Draining, so each file is handed out once.
+#[tauri::command]
+async fn take_pending_files(state: State<'_, PendingFiles>) -> Result<Vec<PathBuf>, String> {
+    Ok(state.take())
+}
+
+/// The contents of a file passed on the command line, for the webview to
+/// transcribe. Only paths handed out through `open-files` or
+/// `take_pending_files` can be read, each once.
+#[tauri::command]
+async fn read_opened_file(state: State<'_, PendingFiles>, path: String) -> Result<tauri::ipc::Response, String> {
+    state.read(Path::new(&path)).map(tauri::ipc::Response::new)
+}
+
+#[tauri::command]
+async fn get_autostart(app: AppHandle) -> Result<bool, String> {
+    app.autolaunch()
+        .is_enabled()
+        .map_err(|e| format!("Failed to read autostart entry: {}", e))
+}
+
+/// Creates or removes the login item. Does nothing if it is already in the
+/// requested state, so repeated calls are safe.
+#[tauri::command]
+async fn set_autostart(app: AppHandle, enabled: bool) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    let current = autolaunch
+        .is_enabled()
+        .map_err(|e| format!("Failed to read autostart entry: {}", e))?;
+    if current == enabled {
+        return Ok(());
+    }
+
+    if enabled {
+        autolaunch
+            .enable()
+            .map_err(|e| format!("Failed to create autostart entry: {}", e))
+    } else {
+        autolaunch
+            .disable()
+            .map_err(|e| format!("Failed to remove autostart entry: {}", e))
+    }
 }
 
 fn handle_global_shortcut(app: &AppHandle, _shortcut: &Shortcut, event: ShortcutEvent) {
-    match event.state {
-        ShortcutState::Pressed => {
-            if let Some(window) = app.get_webview_window("main") {
-                if window.is_visible().unwrap_or(false) {
-                    let _ = app.emit("toggle-recording", {});
-                } else {
-                    let _ = window.show();
-                    let _ = window.set_focus();
-                    let app = app.clone();
-                    std::thread::spawn(move || {
-                        std::thread::sleep(std::time::Duration::from_millis(100));
-                        let _ = app.emit("recording-start", {});
-                    });
-                }
-            }
-        }
-        ShortcutState::Released => {}
+    let mode = app.state::<ShortcutSettings>().mode();
+    let result = match (mode, event.state) {
+        (ShortcutMode::Toggle, ShortcutState::Pressed) => show_and_record(app, true),
+        (ShortcutMode::PushToTalk, ShortcutState::Pressed) => show_and_record(app, false),
+        // Stops even if the press took the show-window path; a release that
+        // beats the delayed start cancels it.
+        (ShortcutMode::PushToTalk, ShortcutState::Released) => recording::stop(app),
+        (ShortcutMode::Toggle, ShortcutState::Released) => Ok(()),
+    };
+    if let Err(e) = result {
+        log!("Failed to handle global shortcut: {}", e);
     }
 }
 
+/// Starts recording, first bringing a hidden window back. With `toggle`, a
+/// press while the window is visible stops a running recording instead.
+fn show_and_record(app: &AppHandle, toggle: bool) -> Result<(), String> {
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    if window.is_visible().unwrap_or(false) {
+        return if toggle {
+            recording::toggle(app, None)
+        } else {
+            recording::start(app, None)
+        };
+    }
+
+    let _ = window.show();
+    let _ = window.set_focus();
+    // Give the webview a moment to come back before it receives the event.
+    recording::start(app, Some(Duration::from_millis(100)))
+}
+
 fn create_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
     let show = MenuItemBuilder::with_id("show", "Show ASR Pro").build(app)?;
     let hide = MenuItemBuilder::with_id("hide", "Hide").build(app)?;
+    let recording = app
+        .try_state::<RecordingState>()
+        .map(|state| state.0.lock().unwrap_or_else(|e| e.into_inner()).phase())
+        .unwrap_or(RecordingPhase::Idle);
+    // A stopping session is already over as far as the user is concerned.
+    let record = if matches!(recording, RecordingPhase::Idle | RecordingPhase::Stopping) {
+        MenuItemBuilder::with_id("start_recording", "Start recording").build(app)?
+    } else {
+        MenuItemBuilder::with_id("stop_recording", "Stop recording").build(app)?
+    };
+    let transcribing = app
+        .try_state::<ActiveTranscriptions>()
+        .is_some_and(|state| !state.is_empty());
+    let cancel = MenuItemBuilder::with_id("cancel_transcription", "Cancel current transcription")
+        .enabled(transcribing)
+        .build(app)?;
     let quit = MenuItemBuilder::with_id("quit", "Quit").build(app)?;
 
     let menu = Menu::new(app)?;
     menu.append(&show)?;
     menu.append(&hide)?;
     menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&record)?;
+    menu.append(&cancel)?;
+    menu.append(&create_recent_submenu(app)?)?;
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
     menu.append(&quit)?;
     Ok(menu)
 }
 
+fn create_recent_submenu(app: &AppHandle) -> tauri::Result<Submenu<tauri::Wry>> {
+    let submenu = Submenu::new(app, "Recent transcripts", true)?;
+    let entries = app
+        .try_state::<RecentTranscriptsState>()
+        .map(|state| state.lock().menu_entries())
+        .unwrap_or_default();
+
+    if entries.is_empty() {
+        let empty = MenuItemBuilder::with_id("recent-empty", "No transcripts yet")
+            .enabled(false)
+            .build(app)?;
+        submenu.append(&empty)?;
+    }
+    for entry in entries {
+        submenu.append(&MenuItemBuilder::with_id(entry.menu_id, entry.label).build(app)?)?;
+    }
+    Ok(submenu)
+}
+
+fn refresh_tray_menu(app: &AppHandle) -> tauri::Result<()> {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        tray.set_menu(Some(create_tray_menu(app)?))?;
+    }
+    Ok(())
+}
+
+fn activate_recent_transcript(app: &AppHandle, menu_id: &str) {
+    let state = app.state::<RecentTranscriptsState>();
+    let (entry, action) = {
+        let list = state.lock();
+        (list.find_by_menu_id(menu_id).cloned(), list.action)
+    };
+    if let Some(entry) = entry {
+        open_recent_transcript(app, &entry, action);
+    }
+}
+
+fn open_recent_transcript(app: &AppHandle, entry: &RecentTranscript, action: RecentTranscriptAction) {
+    let result = match action {
+        RecentTranscriptAction::CopyText => std::fs::read_to_string(&entry.text_path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| app.clipboard().write_text(text).map_err(|e| e.to_string()))
+            .and_then(|_| {
+                notifications::notify(app, "Copied to clipboard", &entry.title, "success", None, None)
+            }),
+        RecentTranscriptAction::OpenFile => app
+            .opener()
+            .open_path(entry.text_path.to_string_lossy(), None::<&str>)
+            .map_err(|e| e.to_string()),
+    };
+
+    if let Err(e) = result {
+        log!("Failed to open recent transcript {}: {}", entry.id, e);
+        let _ = notifications::notify(app, "Transcript unavailable", &e, "error", None, None);
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn run_tray_action(app: &AppHandle, action: TrayAction) {
+    match action {
+        TrayAction::Nothing => {}
+        TrayAction::ShowWindow => show_main_window(app),
+        TrayAction::ToggleRecording => {
+            if let Err(e) = recording::toggle(app, None) {
+                log!("Failed to toggle recording from tray: {}", e);
+            }
+        }
+        TrayAction::CopyLastTranscript => {
+            let latest = app.state::<RecentTranscriptsState>().lock().latest().cloned();
+            match latest {
+                Some(entry) => open_recent_transcript(app, &entry, RecentTranscriptAction::CopyText),
+                None => {
+                    let _ = notifications::notify(app, "No transcripts yet", "", "info", None, None);
+                }
+            }
+        }
+        TrayAction::OpenSettings => {
+            show_main_window(app);
+            let _ = app.emit("navigate", serde_json::json!({ "section": "general" }));
+        }
+    }
+}
+
 fn handle_tray_event(tray: &TrayIcon, event: TrayIconEvent) {
     let app = tray.app_handle();
-    match event {
+    let click = match event {
         TrayIconEvent::Click {
             button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
             ..
-        } => {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
-        }
-        TrayIconEvent::Enter { .. } => {}
-        TrayIconEvent::Leave { .. } => {}
-        TrayIconEvent::DoubleClick { .. } => {}
-        _ => {}
-    }
+        } => TrayClick::Left,
+        TrayIconEvent::Click {
+            button: MouseButton::Middle,
+            button_state: MouseButtonState::Up,
+            ..
+        } => TrayClick::Middle,
+        TrayIconEvent::DoubleClick {
+            button: MouseButton::Left,
+            ..
+        } => TrayClick::Double,
+        _ => return,
+    };
+    let action = app.state::<TrayBehaviorState>().lock().action_for(click);
+    run_tray_action(app, action);
 }
 
 fn handle_tray_menu_event(app: &AppHandle, event: tauri::menu::MenuEvent) {
     match event.id.as_ref() {
-        "show" => {
-            if let Some(window) = app.get_webview_window("main") {
-                let _ = window.show();
-                let _ = window.set_focus();
-            }
-        }
+        "show" => show_main_window(app),
         "hide" => {
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.hide();
             }
         }
+        "start_recording" => {
+            if let Err(e) = show_and_record(app, false) {
+                log!("Failed to start recording from tray: {}", e);
+            }
+        }
+        "stop_recording" => {
+            if let Err(e) = recording::stop(app) {
+                log!("Failed to stop recording from tray: {}", e);
+            }
+        }
+        "cancel_transcription" => {
+            if let Some(task_id) = app.state::<ActiveTranscriptions>().current() {
+                let app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = cancel_transcription_task(&app, task_id).await {
+                        log!("Failed to cancel transcription {}: {}", task_id, e);
+                        let _ = notifications::notify(&app, "Cancel failed", &e, "error", None, None);
+                    }
+                });
+            }
+        }
         "quit" => {
             app.exit(0);
         }
+        id if id.starts_with(recent_transcripts::MENU_ID_PREFIX) => {
+            activate_recent_transcript(app, id);
+        }
         _ => {}
     }
 }
 
+fn offer_crash_reports(app: &AppHandle, crash_dir: &Path) {
+    let reports = crash::pending_reports(crash_dir);
+    if reports.is_empty() {
+        return;
+    }
+    log!("Found {} unreported crash report(s)", reports.len());
+    let reports = crash::mark_reports_seen(&reports);
+    // Reports are named by time, so the last one is the newest.
+    let latest = reports.last().cloned().unwrap_or_default();
+
+    const OPEN: &str = "Open crash folder";
+    const COPY: &str = "Copy report path";
+    const DISMISS: &str = "Dismiss";
+
+    let app_handle = app.clone();
+    let crash_dir: PathBuf = crash_dir.to_path_buf();
+    app.dialog()
+        .message(
+            "ASR Pro closed unexpectedly last time. A crash report was saved locally; \
+             nothing has been sent anywhere. You can attach it to an issue if you like.",
+        )
+        .title("ASR Pro crashed")
+        .kind(MessageDialogKind::Warning)
+        .buttons(MessageDialogButtons::YesNoCancelCustom(
+            OPEN.to_string(),
+            COPY.to_string(),
+            DISMISS.to_string(),
+        ))
+        .show_with_result(move |result| {
+            // Depending on the platform the custom labels come back as-is or
+            // mapped onto Yes/No/Cancel.
+            match result {
+                MessageDialogResult::Yes => open_crash_folder(&app_handle, &crash_dir),
+                MessageDialogResult::Custom(label) if label == OPEN => {
+                    open_crash_folder(&app_handle, &crash_dir)
+                }
+                MessageDialogResult::No => copy_report_path(&app_handle, &latest),
+                MessageDialogResult::Custom(label) if label == COPY => {
+                    copy_report_path(&app_handle, &latest)
+                }
+                _ => {}
+            }
+        });
+}
+
+fn open_crash_folder(app: &AppHandle, crash_dir: &Path) {
+    if let Err(e) = app.opener().open_path(crash_dir.to_string_lossy(), None::<&str>) {
+        log!("Failed to open crash folder: {}", e);
+    }
+}
+
+fn copy_report_path(app: &AppHandle, report: &Path) {
+    if let Err(e) = app.clipboard().write_text(report.to_string_lossy().into_owned()) {
+        log!("Failed to copy crash report path: {}", e);
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    println!("Starting ASR Pro application...");
+    log!("Starting ASR Pro application...");
     
     tauri::Builder::default()
+        // Must come first so a second launch exits before setting anything up.
+        .plugin(tauri_plugin_single_instance::init(instance::handle_second_launch))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_autostart::init(
+            MacosLauncher::LaunchAgent,
+            Some(vec![MINIMIZED_FLAG]),
+        ))
         .plugin(
             tauri_plugin_global_shortcut::Builder::new()
-                .with_shortcuts(["CommandOrControl+Shift+Space"])
-                .unwrap()
                 .with_handler(handle_global_shortcut)
                 .build(),
         )
+        .manage(RecordingState::default())
+        .manage(ActiveTranscriptions::default())
+        .manage(RecordingIndicator::default())
+        .manage(PendingFiles::default())
         .setup(|app| {
-            println!("Setting up tray menu...");
+            let data_dir = app.path().app_data_dir()?;
+            let argv: Vec<String> = std::env::args().collect();
+            let cwd = std::env::current_dir().unwrap_or_default();
+            app.state::<PendingFiles>().push(instance::file_args(&argv, &cwd));
+
+            // The main window starts hidden (see tauri.conf.json) so a login
+            // launch never flashes it on screen.
+            if argv.iter().any(|arg| arg == MINIMIZED_FLAG) {
+                log!("Starting minimized to the tray");
+            } else if let Some(window) = app.get_webview_window("main") {
+                window.show()?;
+            }
+
+            let crash_dir = data_dir.join("crashes");
+            // Only settings without personal content; recent transcripts hold
+            // transcript previews and stay out of crash reports.
+            crash::install_panic_hook(
+                crash_dir.clone(),
+                vec![
+                    data_dir.join("shortcut.json"),
+                    data_dir.join("tray_behavior.json"),
+                    data_dir.join("notifications.json"),
+                    data_dir.join("mic_permission.json"),
+                ],
+            );
+            offer_crash_reports(app.handle(), &crash_dir);
+
+            app.manage(RecentTranscriptsState::load(data_dir.join("recent_transcripts.json")));
+            app.manage(TrayBehaviorState::load(data_dir.join("tray_behavior.json")));
+            app.manage(NotificationState::load(data_dir.join("notifications.json")));
+            app.manage(MicPermission::load(data_dir.join("mic_permission.json")));
+            app.manage(ShortcutSettings::load(data_dir.join("shortcut.json")));
+            register_saved_shortcut(app.handle());
+
+            log!("Setting up tray menu...");
             let tray_menu = create_tray_menu(app.handle())?;
-            let _tray_icon = TrayIconBuilder::new()
-                .menu(&tray_menu)
-                .on_menu_event(handle_tray_menu_event)
-                .on_tray_icon_event(handle_tray_event)
-                .build(app)?;
-            println!("Application setup complete");
+            // tauri.conf.json already creates the "main" tray; building a second
+            // one with the same id would leave tray_by_id pointing at either.
+            let tray = match app.tray_by_id(TRAY_ID) {
+                Some(tray) => tray,
+                None => TrayIconBuilder::with_id(TRAY_ID)
+                    .icon(tray_status::IDLE_ICON)
+                    .icon_as_template(true)
+                    .build(app)?,
+            };
+            tray.set_menu(Some(tray_menu))?;
+            tray.set_tooltip(Some(tray_status::IDLE_TOOLTIP))?;
+            tray.on_menu_event(handle_tray_menu_event);
+            tray.on_tray_icon_event(handle_tray_event);
+
+            let handle = app.handle().clone();
+            app.listen("recording-start", move |_| {
+                handle.state::<RecordingIndicator>().start(&handle);
+                let _ = refresh_tray_menu(&handle);
+            });
+            let handle = app.handle().clone();
+            app.listen("recording-stop", move |_| {
+                handle.state::<RecordingIndicator>().stop(&handle);
+                let _ = refresh_tray_menu(&handle);
+            });
+            log!("Application setup complete");
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -165,8 +749,29 @@ pub fn run() {
             hide_window,
             quit_app,
             show_tray_notification,
+            set_do_not_disturb,
+            get_notification_settings,
+            set_notification_fallback,
+            record_completed_transcript,
+            set_recent_transcript_action,
+            get_microphone_status,
+            set_microphone_consent,
+            report_microphone_access,
+            open_microphone_settings,
+            get_tray_behavior,
+            set_tray_behavior,
+            transcription_started,
+            transcription_finished,
+            cancel_transcription,
+            set_shortcut_mode,
+            set_global_shortcut,
             start_recording,
-            stop_recording
+            stop_recording,
+            copy_to_clipboard,
+            take_pending_files,
+            read_opened_file,
+            get_autostart,
+            set_autostart
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
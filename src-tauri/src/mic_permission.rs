@@ -0,0 +1,278 @@
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings_file::JsonStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MicAccess {
+    Granted,
+    Denied,
+    /// No audio server or input device to capture from.
+    Unavailable,
+    /// Nothing rules the microphone out; the first capture attempt decides.
+    Unknown,
+}
+
+/// Checks whether the system can hand us a microphone at all. The webview's
+/// capture attempt is what triggers the OS prompt, so this only rules out
+/// setups where asking is pointless.
+pub trait MicCapability {
+    fn probe(&self) -> MicAccess;
+}
+
+/// Looks for a PipeWire or PulseAudio socket on Linux; elsewhere the OS
+/// prompt shown on first capture is the only source of truth.
+pub struct SystemMicCapability;
+
+impl MicCapability for SystemMicCapability {
+    #[cfg(target_os = "linux")]
+    fn probe(&self) -> MicAccess {
+        match std::env::var_os("XDG_RUNTIME_DIR") {
+            Some(dir) if !audio_server_running(Path::new(&dir)) => MicAccess::Unavailable,
+            _ => MicAccess::Unknown,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn probe(&self) -> MicAccess {
+        MicAccess::Unknown
+    }
+}
+
+/// Whether PipeWire (which also serves the portal's microphone access) or
+/// PulseAudio is listening in `runtime_dir`.
+pub fn audio_server_running(runtime_dir: &Path) -> bool {
+    runtime_dir.join("pipewire-0").exists() || runtime_dir.join("pulse").join("native").exists()
+}
+
+/// The user's answer to the one-time explanation, saved to
+/// `mic_permission.json`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MicConsent {
+    pub explained: bool,
+    pub declined: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MicStatus {
+    /// Show the explanation before the first capture attempt.
+    pub needs_explanation: bool,
+    pub declined: bool,
+    pub access: MicAccess,
+    pub can_record: bool,
+}
+
+/// Caches what the probe and the webview's capture attempts found out, so a
+/// denial keeps recording disabled until the user asks to try again.
+pub struct MicPermission {
+    consent: JsonStore<MicConsent>,
+    access: Mutex<Option<MicAccess>>,
+}
+
+impl MicPermission {
+    pub fn load(path: PathBuf) -> Self {
+        Self {
+            consent: JsonStore::load(path),
+            access: Mutex::new(None),
+        }
+    }
+
+    fn access(&self) -> MutexGuard<'_, Option<MicAccess>> {
+        self.access.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Current status, probing only if nothing is known yet.
+    pub fn status(&self, capability: &dyn MicCapability) -> MicStatus {
+        let access = *self.access().get_or_insert_with(|| capability.probe());
+        self.status_with(access)
+    }
+
+    /// Forgets the cached result and probes again, e.g. after the user fixed
+    /// their system settings.
+    pub fn recheck(&self, capability: &dyn MicCapability) -> MicStatus {
+        let access = capability.probe();
+        *self.access() = Some(access);
+        self.status_with(access)
+    }
+
+    /// Saves the answer to the explanation. Allowing after an earlier decline
+    /// re-enables recording.
+    pub fn set_consent(&self, allowed: bool) -> Result<(), String> {
+        self.consent.set(MicConsent {
+            explained: true,
+            declined: !allowed,
+        })
+    }
+
+    /// Records how the webview's capture attempt went.
+    pub fn report_capture(&self, granted: bool) {
+        *self.access() = Some(if granted { MicAccess::Granted } else { MicAccess::Denied });
+    }
+
+    fn status_with(&self, access: MicAccess) -> MicStatus {
+        let consent = *self.consent.lock();
+        let needs_explanation = !consent.explained;
+        MicStatus {
+            needs_explanation,
+            declined: consent.declined,
+            access,
+            can_record: !needs_explanation
+                && !consent.declined
+                && matches!(access, MicAccess::Granted | MicAccess::Unknown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::fs;
+
+    struct FakeCapability {
+        access: Cell<MicAccess>,
+        probes: Cell<u32>,
+    }
+
+    impl FakeCapability {
+        fn new(access: MicAccess) -> Self {
+            Self {
+                access: Cell::new(access),
+                probes: Cell::new(0),
+            }
+        }
+    }
+
+    impl MicCapability for FakeCapability {
+        fn probe(&self) -> MicAccess {
+            self.probes.set(self.probes.get() + 1);
+            self.access.get()
+        }
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asrpro-mic-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("mic_permission.json")
+    }
+
+    #[test]
+    fn first_use_needs_the_explanation() {
+        let permission = MicPermission::load(temp_path("first-use"));
+        let status = permission.status(&FakeCapability::new(MicAccess::Unknown));
+        assert!(status.needs_explanation);
+        assert!(!status.can_record);
+    }
+
+    #[test]
+    fn consent_is_remembered_across_loads() {
+        let path = temp_path("remembered");
+        MicPermission::load(path.clone()).set_consent(true).unwrap();
+
+        let status = MicPermission::load(path.clone()).status(&FakeCapability::new(MicAccess::Unknown));
+        assert!(!status.needs_explanation);
+        assert!(status.can_record);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn declining_disables_recording_until_allowed() {
+        let path = temp_path("declined");
+        let permission = MicPermission::load(path.clone());
+        let capability = FakeCapability::new(MicAccess::Granted);
+        permission.set_consent(false).unwrap();
+
+        let status = permission.status(&capability);
+        assert!(status.declined && !status.needs_explanation && !status.can_record);
+
+        permission.set_consent(true).unwrap();
+        assert!(permission.status(&capability).can_record);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn probe_result_is_cached_until_recheck() {
+        let path = temp_path("cached");
+        let permission = MicPermission::load(path.clone());
+        permission.set_consent(true).unwrap();
+        let capability = FakeCapability::new(MicAccess::Unknown);
+
+        permission.status(&capability);
+        permission.status(&capability);
+        assert_eq!(capability.probes.get(), 1);
+
+        permission.recheck(&capability);
+        assert_eq!(capability.probes.get(), 2);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn denied_then_granted_after_fixing_system_settings() {
+        let path = temp_path("denied-granted");
+        let permission = MicPermission::load(path.clone());
+        permission.set_consent(true).unwrap();
+        let capability = FakeCapability::new(MicAccess::Denied);
+
+        let status = permission.status(&capability);
+        assert_eq!(status.access, MicAccess::Denied);
+        assert!(!status.can_record);
+
+        // The user allows the microphone in their system settings; the denial
+        // sticks until they press "try again".
+        capability.access.set(MicAccess::Granted);
+        assert!(!permission.status(&capability).can_record);
+        let status = permission.recheck(&capability);
+        assert_eq!(status.access, MicAccess::Granted);
+        assert!(status.can_record);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn capture_reports_override_an_unknown_probe() {
+        let path = temp_path("capture");
+        let permission = MicPermission::load(path.clone());
+        permission.set_consent(true).unwrap();
+        let capability = FakeCapability::new(MicAccess::Unknown);
+        assert!(permission.status(&capability).can_record);
+
+        permission.report_capture(false);
+        let status = permission.status(&capability);
+        assert_eq!(status.access, MicAccess::Denied);
+        assert!(!status.can_record);
+
+        // Trying again lets the next capture attempt decide.
+        assert!(permission.recheck(&capability).can_record);
+        permission.report_capture(true);
+        assert_eq!(permission.status(&capability).access, MicAccess::Granted);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn unavailable_audio_stack_blocks_recording() {
+        let path = temp_path("unavailable");
+        let permission = MicPermission::load(path.clone());
+        permission.set_consent(true).unwrap();
+        let status = permission.status(&FakeCapability::new(MicAccess::Unavailable));
+        assert!(!status.can_record);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn detects_pipewire_and_pulse_sockets() {
+        let dir = temp_path("sockets").parent().unwrap().to_path_buf();
+        fs::create_dir_all(dir.join("pulse")).unwrap();
+        assert!(!audio_server_running(&dir));
+
+        fs::write(dir.join("pulse").join("native"), b"").unwrap();
+        assert!(audio_server_running(&dir));
+        fs::remove_file(dir.join("pulse").join("native")).unwrap();
+
+        fs::write(dir.join("pipewire-0"), b"").unwrap();
+        assert!(audio_server_running(&dir));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::settings_file::JsonStore;
+
+/// Smallest progress change (as a fraction) worth redrawing a notification for.
+const MIN_PROGRESS_STEP: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    Low,
+    Normal,
+    Critical,
+}
+
+impl Urgency {
+    /// Maps the frontend's notification types (info/success/warning/error).
+    pub fn from_notification_type(notification_type: &str) -> Self {
+        match notification_type {
+            "error" => Urgency::Critical,
+            "warning" | "success" => Urgency::Normal,
+            _ => Urgency::Low,
+        }
+    }
+}
+
+/// Outcome of feeding a notification request through the tracker.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NotificationAction {
+    /// Show the notification, replacing any earlier one with the same `native_id`.
+    /// `replaces` is set when that earlier notification is still on screen.
+    Show {
+        native_id: u32,
+        progress: Option<f64>,
+        is_final: bool,
+        replaces: bool,
+    },
+    /// The progress moved too little since the last update to be worth showing.
+    Skip,
+}
+
+struct ActiveNotification {
+    native_id: u32,
+    last_progress: Option<f64>,
+}
+
+/// Coalesces repeated notifications that share a caller-provided id into a
+/// single native notification, independent of the OS notification call.
+///
+/// A request carrying a progress below 1.0 keeps the notification open; a
+/// request with no progress or a progress of 1.0 finalises it and frees the id.
+#[derive(Default)]
+pub struct NotificationTracker {
+    last_native_id: u32,
+    active: HashMap<String, ActiveNotification>,
+}
+
+impl NotificationTracker {
+    pub fn update(&mut self, id: Option<&str>, progress: Option<f64>) -> NotificationAction {
+        let progress = progress.map(|p| p.clamp(0.0, 1.0));
+        let is_final = progress.is_none_or(|p| p >= 1.0);
+
+        let Some(id) = id else {
+            return NotificationAction::Show {
+                native_id: self.allocate_id(),
+                progress,
+                is_final: true,
+                replaces: false,
+            };
+        };
+
+        if is_final {
+            let (native_id, replaces) = match self.active.remove(id) {
+                Some(entry) => (entry.native_id, true),
+                None => (self.allocate_id(), false),
+            };
+            return NotificationAction::Show {
+                native_id,
+                progress,
+                is_final: true,
+                replaces,
+            };
+        }
+
+        if let Some(entry) = self.active.get_mut(id) {
+            if let (Some(last), Some(current)) = (entry.last_progress, progress) {
+                if (current - last).abs() < MIN_PROGRESS_STEP {
+                    return NotificationAction::Skip;
+                }
+            }
+            entry.last_progress = progress;
+            return NotificationAction::Show {
+                native_id: entry.native_id,
+                progress,
+                is_final: false,
+                replaces: true,
+            };
+        }
+
+        let native_id = self.allocate_id();
+        self.active.insert(
+            id.to_string(),
+            ActiveNotification {
+                native_id,
+                last_progress: progress,
+            },
+        );
+        NotificationAction::Show {
+            native_id,
+            progress,
+            is_final: false,
+            replaces: false,
+        }
+    }
+
+    /// Drops an in-progress notification that was never shown natively, so
+    /// its next update isn't treated as replacing one on screen.
+    pub fn forget(&mut self, id: &str) {
+        self.active.remove(id);
+    }
+
+    fn allocate_id(&mut self) -> u32 {
+        // 0 means "no replacement" to notification servers, so never hand it out.
+        self.last_native_id = self.last_native_id.checked_add(1).unwrap_or(1);
+        self.last_native_id
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    /// When a native notification fails, show the in-app toast instead of
+    /// reporting the failure to the caller.
+    pub webview_fallback: bool,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self { webview_fallback: true }
+    }
+}
+
+pub struct NotificationState {
+    settings: JsonStore<NotificationSettings>,
+    pub tracker: Mutex<NotificationTracker>,
+    pub do_not_disturb: AtomicBool,
+}
+
+impl NotificationState {
+    pub fn load(path: PathBuf) -> Self {
+        Self {
+            settings: JsonStore::load(path),
+            tracker: Mutex::default(),
+            do_not_disturb: AtomicBool::new(false),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, NotificationSettings> {
+        self.settings.lock()
+    }
+
+    pub fn set_webview_fallback(&self, enabled: bool) -> Result<(), String> {
+        self.settings.update(|settings| settings.webview_fallback = enabled)
+    }
+
+    /// Critical notifications still get through while do-not-disturb is on.
+    pub fn allows(&self, urgency: Urgency) -> bool {
+        urgency == Urgency::Critical || !self.do_not_disturb.load(Ordering::Relaxed)
+    }
+}
+
+/// Shows a notification natively, or as an in-app toast (the
+/// `show-notification` event) when a native one isn't wanted: while the main
+/// window is visible, unless it updates a native notification already on
+/// screen, and while do-not-disturb suppresses it. Native failures (no
+/// notification daemon, permission denied) are logged and, with the webview
+/// fallback enabled, shown as a toast instead; otherwise the error is returned.
+pub fn notify(
+    app: &AppHandle,
+    title: &str,
+    message: &str,
+    notification_type: &str,
+    id: Option<&str>,
+    progress: Option<f64>,
+) -> Result<(), String> {
+    let toast = || {
+        app.emit("show-notification", serde_json::json!({
+            "id": id,
+            "title": title,
+            "message": message,
+            "type": notification_type,
+            "progress": progress
+        })).map_err(|e| e.to_string())
+    };
+
+    let state = app.state::<NotificationState>();
+    let urgency = Urgency::from_notification_type(notification_type);
+    if !state.allows(urgency) {
+        return toast();
+    }
+
+    let mut tracker = state.tracker.lock().map_err(|e| e.to_string())?;
+    let NotificationAction::Show { native_id, progress: shown_progress, is_final, replaces } =
+        tracker.update(id, progress)
+    else {
+        return Ok(());
+    };
+    if !replaces && main_window_visible(app) {
+        if let (Some(id), false) = (id, is_final) {
+            tracker.forget(id);
+        }
+        return toast();
+    }
+    drop(tracker);
+
+    if let Err(e) = show_native(app, native_id, title, message, urgency, shown_progress, is_final) {
+        log!("Failed to show native notification '{}': {}", title, e);
+        if !state.lock().webview_fallback {
+            return Err(e);
+        }
+        return toast();
+    }
+
+    Ok(())
+}
+
+fn main_window_visible(app: &AppHandle) -> bool {
+    app.get_webview_window("main")
+        .is_some_and(|window| window.is_visible().unwrap_or(false))
+}
+
+fn show_native(
+    app: &AppHandle,
+    native_id: u32,
+    title: &str,
+    message: &str,
+    urgency: Urgency,
+    progress: Option<f64>,
+    is_final: bool,
+) -> Result<(), String> {
+    let body = match progress {
+        Some(p) if !is_final => format!("{} ({:.0}%)", message, p * 100.0),
+        _ => message.to_string(),
+    };
+
+    let mut notification = notify_rust::Notification::new();
+    notification
+        .appname("ASR Pro")
+        .summary(title)
+        .body(&body)
+        .id(native_id);
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        notification.urgency(match urgency {
+            Urgency::Low => notify_rust::Urgency::Low,
+            Urgency::Normal => notify_rust::Urgency::Normal,
+            Urgency::Critical => notify_rust::Urgency::Critical,
+        });
+        if let Some(p) = progress.filter(|_| !is_final) {
+            notification.hint(notify_rust::Hint::CustomInt(
+                "value".to_string(),
+                (p * 100.0).round() as i32,
+            ));
+        }
+        if is_final {
+            notification.action("default", "Open ASR Pro");
+        }
+
+        let handle = notification.show().map_err(|e| e.to_string())?;
+        if is_final {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                handle.wait_for_action(|action| {
+                    if action == "default" {
+                        if let Some(window) = app.get_webview_window("main") {
+                            let _ = window.show();
+                            let _ = window.set_focus();
+                        }
+                    }
+                });
+            });
+        }
+        Ok(())
+    }
+
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    {
+        let _ = (app, urgency);
+        notification.show().map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shown(action: NotificationAction) -> (u32, bool, bool) {
+        match action {
+            NotificationAction::Show { native_id, is_final, replaces, .. } => {
+                (native_id, is_final, replaces)
+            }
+            NotificationAction::Skip => panic!("expected the notification to be shown"),
+        }
+    }
+
+    #[test]
+    fn coalesces_updates_with_the_same_id() {
+        let mut tracker = NotificationTracker::default();
+        let (first, is_final, replaces) = shown(tracker.update(Some("download"), Some(0.1)));
+        assert!(!is_final);
+        assert!(!replaces);
+
+        let (second, _, replaces) = shown(tracker.update(Some("download"), Some(0.5)));
+        assert_eq!(second, first);
+        assert!(replaces);
+
+        let (other, _, _) = shown(tracker.update(Some("transcribe"), Some(0.5)));
+        assert_ne!(other, first);
+    }
+
+    #[test]
+    fn skips_progress_changes_below_one_percent() {
+        let mut tracker = NotificationTracker::default();
+        tracker.update(Some("download"), Some(0.50));
+        assert_eq!(tracker.update(Some("download"), Some(0.505)), NotificationAction::Skip);
+        assert_ne!(tracker.update(Some("download"), Some(0.52)), NotificationAction::Skip);
+    }
+
+    #[test]
+    fn completion_finalises_and_frees_the_id() {
+        let mut tracker = NotificationTracker::default();
+        let (native_id, _, _) = shown(tracker.update(Some("download"), Some(0.3)));
+
+        let (final_id, is_final, replaces) = shown(tracker.update(Some("download"), Some(1.0)));
+        assert_eq!(final_id, native_id);
+        assert!(is_final);
+        assert!(replaces);
+
+        // The id is free again, so the next run starts a new notification.
+        let (next_id, _, replaces) = shown(tracker.update(Some("download"), Some(0.1)));
+        assert_ne!(next_id, native_id);
+        assert!(!replaces);
+    }
+
+    #[test]
+    fn missing_progress_finalises_too() {
+        let mut tracker = NotificationTracker::default();
+        let (native_id, _, _) = shown(tracker.update(Some("transcribe"), Some(0.9)));
+        let (final_id, is_final, _) = shown(tracker.update(Some("transcribe"), None));
+        assert_eq!(final_id, native_id);
+        assert!(is_final);
+        assert!(tracker.active.is_empty());
+    }
+
+    #[test]
+    fn notifications_without_an_id_are_always_final() {
+        let mut tracker = NotificationTracker::default();
+        let (first, is_final, _) = shown(tracker.update(None, Some(0.2)));
+        let (second, _, _) = shown(tracker.update(None, Some(0.2)));
+        assert!(is_final);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn never_hands_out_native_id_zero() {
+        let mut tracker = NotificationTracker {
+            last_native_id: u32::MAX - 1,
+            ..Default::default()
+        };
+        let ids: Vec<u32> = (0..3).map(|_| shown(tracker.update(None, None)).0).collect();
+        assert_eq!(ids, vec![u32::MAX, 1, 2]);
+    }
+
+    #[test]
+    fn forgotten_notifications_start_over() {
+        let mut tracker = NotificationTracker::default();
+        let (first, _, _) = shown(tracker.update(Some("download"), Some(0.1)));
+        tracker.forget("download");
+
+        let (second, _, replaces) = shown(tracker.update(Some("download"), Some(0.5)));
+        assert_ne!(second, first);
+        assert!(!replaces);
+    }
+
+    #[test]
+    fn do_not_disturb_only_lets_critical_through() {
+        let dir = std::env::temp_dir().join(format!("asrpro-notifications-{}", std::process::id()));
+        let state = NotificationState::load(dir.join("missing.json"));
+        state.do_not_disturb.store(true, Ordering::Relaxed);
+        assert!(state.allows(Urgency::Critical));
+        assert!(!state.allows(Urgency::Normal));
+        assert!(!state.allows(Urgency::Low));
+    }
+
+    #[test]
+    fn webview_fallback_is_persisted() {
+        let dir = std::env::temp_dir().join(format!("asrpro-notification-settings-{}", std::process::id()));
+        let path = dir.join("notifications.json");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let state = NotificationState::load(path.clone());
+        assert!(state.lock().webview_fallback);
+        state.set_webview_fallback(false).unwrap();
+
+        assert!(!NotificationState::load(path).lock().webview_fallback);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
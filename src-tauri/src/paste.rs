@@ -0,0 +1,38 @@
+use std::time::Duration;
+
+use enigo::{Direction, Enigo, Key, Keyboard, Settings};
+use tauri::{AppHandle, Manager};
+
+/// How long the OS gets to hand focus back before the keystroke is sent.
+const FOCUS_RESTORE_DELAY: Duration = Duration::from_millis(150);
+
+#[cfg(target_os = "macos")]
+const PASTE_MODIFIER: Key = Key::Meta;
+#[cfg(not(target_os = "macos"))]
+const PASTE_MODIFIER: Key = Key::Control;
+
+/// Sends Ctrl+V (Cmd+V on macOS) to whichever app had focus before ASR Pro.
+///
+/// If our own window is focused it is hidden first so the window manager
+/// returns focus to the previous app; otherwise the paste would land in the
+/// webview. Blocks for a short moment, so call it off the main thread.
+pub fn paste_into_previous_app(app: &AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window("main") {
+        if window.is_focused().unwrap_or(false) {
+            window.hide().map_err(|e| e.to_string())?;
+            #[cfg(target_os = "macos")]
+            app.hide().map_err(|e| e.to_string())?;
+            std::thread::sleep(FOCUS_RESTORE_DELAY);
+        }
+    }
+
+    let mut enigo = Enigo::new(&Settings::default())
+        .map_err(|e| format!("Failed to set up keyboard input: {}", e))?;
+    enigo
+        .key(PASTE_MODIFIER, Direction::Press)
+        .map_err(|e| format!("Failed to send paste shortcut: {}", e))?;
+    let result = enigo.key(Key::Unicode('v'), Direction::Click);
+    // Always release the modifier, even if the V press failed.
+    let _ = enigo.key(PASTE_MODIFIER, Direction::Release);
+    result.map_err(|e| format!("Failed to send paste shortcut: {}", e))
+}
@@ -0,0 +1,353 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+use crate::settings_file::JsonStore;
+
+pub const MAX_ENTRIES: usize = 5;
+pub const MENU_ID_PREFIX: &str = "recent:";
+const PREVIEW_CHARS: usize = 200;
+const TITLE_CHARS: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecentTranscriptAction {
+    #[default]
+    CopyText,
+    OpenFile,
+}
+
+/// A completed transcription as remembered by the tray. Only a short preview
+/// is stored; the full text stays in the file at `text_path`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecentTranscript {
+    pub id: String,
+    pub title: String,
+    pub text_path: PathBuf,
+    pub preview: String,
+    pub completed_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentMenuEntry {
+    pub menu_id: String,
+    pub label: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecentTranscripts {
+    #[serde(default)]
+    pub action: RecentTranscriptAction,
+    #[serde(default)]
+    entries: Vec<RecentTranscript>,
+}
+
+impl RecentTranscripts {
+    /// Adds `entry` as the newest transcript, replacing an older entry with the
+    /// same id. Entries beyond `MAX_ENTRIES` are dropped and returned, minus any
+    /// whose file a remaining entry still points at.
+    pub fn record(&mut self, entry: RecentTranscript) -> Vec<RecentTranscript> {
+        self.entries.retain(|e| e.id != entry.id);
+        self.entries.insert(0, entry);
+        if self.entries.len() <= MAX_ENTRIES {
+            return Vec::new();
+        }
+        let mut evicted = self.entries.split_off(MAX_ENTRIES);
+        evicted.retain(|old| self.entries.iter().all(|e| e.text_path != old.text_path));
+        evicted
+    }
+
+    pub fn latest(&self) -> Option<&RecentTranscript> {
+        self.entries.first()
+    }
+
+    pub fn find_by_menu_id(&self, menu_id: &str) -> Option<&RecentTranscript> {
+        let id = menu_id.strip_prefix(MENU_ID_PREFIX)?;
+        self.entries.iter().find(|e| e.id == id)
+    }
+
+    pub fn menu_entries(&self) -> Vec<RecentMenuEntry> {
+        self.entries
+            .iter()
+            .map(|entry| RecentMenuEntry {
+                menu_id: format!("{}{}", MENU_ID_PREFIX, entry.id),
+                label: menu_label(entry),
+            })
+            .collect()
+    }
+}
+
+fn menu_label(entry: &RecentTranscript) -> String {
+    let title = truncate(entry.title.trim(), TITLE_CHARS);
+    match Local.timestamp_opt(entry.completed_at, 0).single() {
+        Some(time) => format!("{} — {}", title, time.format("%b %d %H:%M")),
+        None => title,
+    }
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// Writes a transcript that only exists in the webview to `dir/<id>.txt`, so
+/// the tray can copy or open it later. `id` must be a plain file-name stem.
+pub fn save_text(dir: &Path, id: &str, text: &str) -> Result<PathBuf, String> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid transcript id '{}'", id));
+    }
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("{}.txt", id));
+    fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Resolves a webview-supplied transcript path, accepting only files inside
+/// `dir` so the tray can't be pointed at arbitrary files.
+pub fn resolve_text_path(dir: &Path, path: &Path) -> Result<PathBuf, String> {
+    let dir = dir.canonicalize().map_err(|e| e.to_string())?;
+    let resolved = path
+        .canonicalize()
+        .map_err(|e| format!("Invalid transcript path {}: {}", path.display(), e))?;
+    if !resolved.starts_with(&dir) || !resolved.is_file() {
+        return Err(format!("Transcript path {} is outside {}", path.display(), dir.display()));
+    }
+    Ok(resolved)
+}
+
+/// Deletes the files of evicted transcripts that `save_text` wrote to `dir`;
+/// files elsewhere are left alone.
+pub fn remove_saved_texts(dir: &Path, evicted: &[RecentTranscript]) {
+    let Ok(dir) = dir.canonicalize() else {
+        return;
+    };
+    for entry in evicted {
+        let Ok(path) = entry.text_path.canonicalize() else {
+            continue;
+        };
+        if path.starts_with(&dir) {
+            if let Err(e) = fs::remove_file(&path) {
+                log!("Failed to remove transcript {}: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Reads at most the first `PREVIEW_CHARS` characters of a transcript file.
+pub fn read_preview(path: &Path) -> Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| e.to_string())?;
+    // Four bytes per char is the UTF-8 worst case.
+    let mut buf = Vec::with_capacity(PREVIEW_CHARS * 4);
+    file.take((PREVIEW_CHARS * 4) as u64)
+        .read_to_end(&mut buf)
+        .map_err(|e| e.to_string())?;
+    let text = String::from_utf8_lossy(&buf);
+    Ok(truncate(text.trim(), PREVIEW_CHARS))
+}
+
+/// The recent list, saved to `recent_transcripts.json`.
+pub type RecentTranscriptsState = JsonStore<RecentTranscripts>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transcript(id: &str, title: &str) -> RecentTranscript {
+        RecentTranscript {
+            id: id.to_string(),
+            title: title.to_string(),
+            text_path: PathBuf::from(format!("/tmp/{}.txt", id)),
+            preview: String::new(),
+            completed_at: 1_700_000_000,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asrpro-recent-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn record_keeps_newest_first_and_caps_the_list() {
+        let mut list = RecentTranscripts::default();
+        for i in 0..MAX_ENTRIES + 2 {
+            list.record(transcript(&format!("t{}", i), "Meeting"));
+        }
+        let ids: Vec<&str> = list.entries.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["t6", "t5", "t4", "t3", "t2"]);
+        assert_eq!(list.latest().map(|e| e.id.as_str()), Some("t6"));
+    }
+
+    #[test]
+    fn record_returns_evicted_entries() {
+        let mut list = RecentTranscripts::default();
+        for i in 0..MAX_ENTRIES {
+            assert!(list.record(transcript(&format!("t{}", i), "Meeting")).is_empty());
+        }
+        let evicted = list.record(transcript("t5", "Meeting"));
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].id, "t0");
+
+        // A file still used by a remaining entry is not reported.
+        let mut shared = transcript("t6", "Meeting");
+        shared.text_path = list.entries[MAX_ENTRIES - 1].text_path.clone();
+        assert!(list.record(shared).is_empty());
+    }
+
+    #[test]
+    fn record_replaces_an_entry_with_the_same_id() {
+        let mut list = RecentTranscripts::default();
+        list.record(transcript("a", "First"));
+        list.record(transcript("b", "Second"));
+        list.record(transcript("a", "First again"));
+        assert_eq!(list.entries.len(), 2);
+        assert_eq!(list.entries[0].title, "First again");
+        assert_eq!(list.entries[1].id, "b");
+    }
+
+    #[test]
+    fn menu_entries_map_back_to_transcripts() {
+        let mut list = RecentTranscripts::default();
+        list.record(transcript("a", "Standup"));
+        list.record(transcript("b", "Lecture"));
+
+        let entries = list.menu_entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].menu_id, "recent:b");
+        assert!(entries[0].label.starts_with("Lecture — "));
+        assert_eq!(
+            list.find_by_menu_id(&entries[1].menu_id).map(|e| e.id.as_str()),
+            Some("a")
+        );
+        assert!(list.find_by_menu_id("show").is_none());
+        assert!(list.find_by_menu_id("recent:missing").is_none());
+    }
+
+    #[test]
+    fn menu_label_truncates_long_titles() {
+        let mut entry = transcript("a", &"x".repeat(40));
+        entry.completed_at = i64::MAX;
+        let label = menu_label(&entry);
+        assert_eq!(label.chars().count(), TITLE_CHARS);
+        assert!(label.ends_with('…'));
+    }
+
+    #[test]
+    fn truncate_only_shortens_long_text() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly10!", 10), "exactly10!");
+        assert_eq!(truncate("héllo wörld", 6), "héllo…");
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = temp_dir("round-trip");
+        let path = dir.join("recent_transcripts.json");
+        let state = RecentTranscriptsState::load(path.clone());
+        state
+            .update(|list| {
+                list.action = RecentTranscriptAction::OpenFile;
+                list.record(transcript("a", "Standup"));
+            })
+            .unwrap();
+
+        let loaded = RecentTranscriptsState::load(path).lock().clone();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded.action, RecentTranscriptAction::OpenFile);
+        assert_eq!(loaded.entries, state.lock().entries);
+    }
+
+    #[test]
+    fn load_falls_back_to_empty() {
+        let dir = temp_dir("corrupt");
+        let path = dir.join("recent_transcripts.json");
+        assert!(RecentTranscriptsState::load(path.clone()).lock().entries.is_empty());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "not json").unwrap();
+        let loaded = RecentTranscriptsState::load(path).lock().clone();
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(loaded.entries.is_empty());
+        assert_eq!(loaded.action, RecentTranscriptAction::CopyText);
+    }
+
+    #[test]
+    fn save_text_writes_a_preview_able_file() {
+        let dir = temp_dir("save-text");
+        let path = save_text(&dir, "rec-1", "  hello world  ").unwrap();
+        assert_eq!(path, dir.join("rec-1.txt"));
+        assert_eq!(read_preview(&path).unwrap(), "hello world");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn evicted_saved_texts_are_deleted() {
+        let dir = temp_dir("evict");
+        let outside_dir = temp_dir("evict-outside");
+        fs::create_dir_all(&outside_dir).unwrap();
+        let outside = outside_dir.join("keep.txt");
+        fs::write(&outside, "mine").unwrap();
+
+        let mut list = RecentTranscripts::default();
+        let mut external = transcript("ext", "External");
+        external.text_path = outside.clone();
+        list.record(external);
+        let mut evicted = Vec::new();
+        for i in 0..MAX_ENTRIES + 1 {
+            let id = format!("t{}", i);
+            let mut entry = transcript(&id, "Meeting");
+            entry.text_path = save_text(&dir, &id, "text").unwrap();
+            evicted.extend(list.record(entry));
+        }
+        let ids: Vec<&str> = evicted.iter().map(|e| e.id.as_str()).collect();
+        assert_eq!(ids, vec!["ext", "t0"]);
+
+        remove_saved_texts(&dir, &evicted);
+        assert!(!dir.join("t0.txt").exists());
+        assert!(dir.join("t1.txt").exists());
+        assert!(outside.exists());
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside_dir).unwrap();
+    }
+
+    #[test]
+    fn text_paths_must_stay_in_the_transcripts_dir() {
+        let dir = temp_dir("resolve");
+        let saved = save_text(&dir, "rec-1", "hello").unwrap();
+        assert_eq!(
+            resolve_text_path(&dir, &saved).unwrap(),
+            saved.canonicalize().unwrap()
+        );
+
+        let outside_dir = temp_dir("resolve-outside");
+        fs::create_dir_all(&outside_dir).unwrap();
+        fs::write(outside_dir.join("secret.txt"), "x").unwrap();
+        assert!(resolve_text_path(&dir, &outside_dir.join("secret.txt")).is_err());
+        let escape = dir.join("..").join(outside_dir.file_name().unwrap()).join("secret.txt");
+        assert!(resolve_text_path(&dir, &escape).is_err());
+        assert!(resolve_text_path(&dir, &dir.join("missing.txt")).is_err());
+        assert!(resolve_text_path(&dir, &dir).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside_dir).unwrap();
+    }
+
+    #[test]
+    fn save_text_rejects_ids_that_are_not_file_names() {
+        let dir = temp_dir("bad-id");
+        assert!(save_text(&dir, "../escape", "x").is_err());
+        assert!(save_text(&dir, "", "x").is_err());
+        assert!(!dir.exists());
+    }
+}
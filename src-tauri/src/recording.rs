@@ -0,0 +1,361 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use tauri::{AppHandle, Emitter, Manager};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingPhase {
+    Idle,
+    Starting,
+    Recording,
+    Stopping,
+}
+
+/// One start/stop pair, correlated across the two events by `id`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordingSession {
+    pub id: Uuid,
+    pub started_at: DateTime<Utc>,
+}
+
+impl RecordingSession {
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            started_at,
+        }
+    }
+
+    pub fn duration_ms(&self, stopped_at: DateTime<Utc>) -> i64 {
+        (stopped_at - self.started_at).num_milliseconds().max(0)
+    }
+}
+
+/// What an accepted stop request has to do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StopRequest {
+    /// End `session` and emit `recording-stop`, confirming with `generation`.
+    Stop { generation: u64, session: RecordingSession },
+    /// The start was still pending, so nothing was emitted and nothing needs
+    /// to be stopped.
+    CancelStart,
+}
+
+/// Tracks the recording lifecycle so racing start/stop requests from the
+/// shortcut handler, the tray and the webview can't emit conflicting events.
+///
+/// Every accepted transition bumps `generation`; the frontend can drop any
+/// event whose generation is older than the last one it has seen.
+#[derive(Debug)]
+pub struct RecordingStateMachine {
+    phase: RecordingPhase,
+    generation: u64,
+    session: Option<RecordingSession>,
+}
+
+impl Default for RecordingStateMachine {
+    fn default() -> Self {
+        Self {
+            phase: RecordingPhase::Idle,
+            generation: 0,
+            session: None,
+        }
+    }
+}
+
+impl RecordingStateMachine {
+    pub fn phase(&self) -> RecordingPhase {
+        self.phase
+    }
+
+    /// Idle → Starting. Returns the generation the start must be confirmed with,
+    /// or an error naming the phase that is in the way.
+    pub fn begin_start(&mut self) -> Result<u64, String> {
+        match (self.phase, &self.session) {
+            (RecordingPhase::Idle, _) => {}
+            (RecordingPhase::Starting, _) => return Err("Recording is already starting".to_string()),
+            (RecordingPhase::Recording, Some(session)) => {
+                return Err(format!("Recording already in progress (session {})", session.id))
+            }
+            (RecordingPhase::Recording, None) => return Err("Recording already in progress".to_string()),
+            (RecordingPhase::Stopping, _) => {
+                return Err("The last recording is still stopping; try again in a moment".to_string())
+            }
+        }
+        self.phase = RecordingPhase::Starting;
+        self.generation += 1;
+        Ok(self.generation)
+    }
+
+    /// Starting → Recording with `session`, only if nothing else happened
+    /// since `begin_start`.
+    pub fn confirm_start(&mut self, generation: u64, session: RecordingSession) -> bool {
+        if self.phase != RecordingPhase::Starting || self.generation != generation {
+            return false;
+        }
+        self.phase = RecordingPhase::Recording;
+        self.session = Some(session);
+        true
+    }
+
+    /// Recording → Stopping, handing back the session to end. Stopping a
+    /// pending start cancels it and goes straight back to Idle. Errors if
+    /// there is no session to stop.
+    pub fn begin_stop(&mut self) -> Result<StopRequest, String> {
+        let no_session = || "No active recording session".to_string();
+        match self.phase {
+            RecordingPhase::Starting => {
+                self.phase = RecordingPhase::Idle;
+                self.generation += 1;
+                Ok(StopRequest::CancelStart)
+            }
+            RecordingPhase::Recording => {
+                let session = self.session.take().ok_or_else(no_session)?;
+                self.phase = RecordingPhase::Stopping;
+                self.generation += 1;
+                Ok(StopRequest::Stop {
+                    generation: self.generation,
+                    session,
+                })
+            }
+            RecordingPhase::Idle | RecordingPhase::Stopping => Err(no_session()),
+        }
+    }
+
+    /// Stopping → Idle.
+    pub fn confirm_stop(&mut self, generation: u64) -> bool {
+        if self.phase != RecordingPhase::Stopping || self.generation != generation {
+            return false;
+        }
+        self.phase = RecordingPhase::Idle;
+        true
+    }
+}
+
+#[derive(Default)]
+pub struct RecordingState(pub Mutex<RecordingStateMachine>);
+
+fn with_machine<T>(app: &AppHandle, f: impl FnOnce(&mut RecordingStateMachine) -> T) -> T {
+    let state = app.state::<RecordingState>();
+    let mut machine = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut machine)
+}
+
+/// Emits `recording-start` with a new session, optionally after `delay` (used
+/// when the window has just been shown). A stop arriving during the delay
+/// cancels the emit.
+///
+/// Starting while a session is already starting or running is an error; the
+/// running session is left alone rather than stopped implicitly.
+pub fn start(app: &AppHandle, delay: Option<Duration>) -> Result<(), String> {
+    let generation = with_machine(app, |m| m.begin_start())?;
+
+    match delay {
+        None => emit_start(app, generation),
+        Some(delay) => {
+            let app = app.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                if let Err(e) = emit_start(&app, generation) {
+                    log!("Failed to emit delayed recording-start: {}", e);
+                }
+            });
+            Ok(())
+        }
+    }
+}
+
+/// Ends the active session and emits `recording-stop`. Errors if there is
+/// no session to stop.
+pub fn stop(app: &AppHandle) -> Result<(), String> {
+    finish(app, false)
+}
+
+/// Like `stop`, but marks the event `cancelled` so the webview throws the
+/// audio away instead of transcribing it.
+pub fn cancel(app: &AppHandle) -> Result<(), String> {
+    finish(app, true)
+}
+
+fn finish(app: &AppHandle, cancelled: bool) -> Result<(), String> {
+    let (generation, session) = match with_machine(app, |m| m.begin_stop())? {
+        StopRequest::Stop { generation, session } => (generation, session),
+        StopRequest::CancelStart => {
+            log!("Cancelled pending recording start");
+            return Ok(());
+        }
+    };
+
+    let stopped_at = Utc::now();
+    let result = app
+        .emit(
+            "recording-stop",
+            serde_json::json!({
+                "generation": generation,
+                "session_id": session.id.to_string(),
+                "stopped_at": timestamp(stopped_at),
+                "duration_ms": session.duration_ms(stopped_at),
+                "cancelled": cancelled,
+            }),
+        )
+        .map_err(|e| e.to_string());
+    with_machine(app, |m| m.confirm_stop(generation));
+    result
+}
+
+pub fn toggle(app: &AppHandle, delay: Option<Duration>) -> Result<(), String> {
+    match with_machine(app, |m| m.phase()) {
+        RecordingPhase::Idle => start(app, delay),
+        RecordingPhase::Starting | RecordingPhase::Recording => stop(app),
+        RecordingPhase::Stopping => Ok(()),
+    }
+}
+
+fn emit_start(app: &AppHandle, generation: u64) -> Result<(), String> {
+    let session = RecordingSession::new(Utc::now());
+    if !with_machine(app, |m| m.confirm_start(generation, session.clone())) {
+        log!("Dropping stale recording-start (generation {})", generation);
+        return Ok(());
+    }
+    app.emit(
+        "recording-start",
+        serde_json::json!({
+            "generation": generation,
+            "session_id": session.id.to_string(),
+            "started_at": timestamp(session.started_at),
+        }),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> RecordingSession {
+        RecordingSession::new(Utc::now())
+    }
+
+    #[test]
+    fn second_start_is_an_error() {
+        let mut machine = RecordingStateMachine::default();
+        let generation = machine.begin_start().unwrap();
+        assert_eq!(machine.begin_start(), Err("Recording is already starting".to_string()));
+
+        let started = session();
+        assert!(machine.confirm_start(generation, started.clone()));
+        assert_eq!(
+            machine.begin_start(),
+            Err(format!("Recording already in progress (session {})", started.id))
+        );
+        assert_eq!(machine.phase(), RecordingPhase::Recording);
+        assert_eq!(machine.session, Some(started));
+
+        let Ok(StopRequest::Stop { generation, .. }) = machine.begin_stop() else {
+            panic!("expected a stop request");
+        };
+        assert_eq!(
+            machine.begin_start(),
+            Err("The last recording is still stopping; try again in a moment".to_string())
+        );
+        assert_eq!(machine.phase(), RecordingPhase::Stopping);
+
+        assert!(machine.confirm_stop(generation));
+        assert!(machine.begin_start().is_ok());
+    }
+
+    #[test]
+    fn stop_without_a_session_is_an_error() {
+        let mut machine = RecordingStateMachine::default();
+        let error = Err("No active recording session".to_string());
+        assert_eq!(machine.begin_stop(), error);
+        assert_eq!(machine.phase(), RecordingPhase::Idle);
+
+        let generation = machine.begin_start().unwrap();
+        assert!(machine.confirm_start(generation, session()));
+        let Ok(StopRequest::Stop { generation, .. }) = machine.begin_stop() else {
+            panic!("expected a stop");
+        };
+        assert!(machine.confirm_stop(generation));
+        assert_eq!(machine.begin_stop(), error);
+    }
+
+    #[test]
+    fn duration_is_measured_from_the_start() {
+        let started = session();
+        let stopped_at = started.started_at + chrono::Duration::milliseconds(1_250);
+        assert_eq!(started.duration_ms(stopped_at), 1_250);
+        assert_eq!(started.duration_ms(started.started_at), 0);
+        // A clock that stepped backwards never yields a negative duration.
+        let earlier = started.started_at - chrono::Duration::seconds(5);
+        assert_eq!(started.duration_ms(earlier), 0);
+    }
+
+    #[test]
+    fn start_stop_start_drops_the_first_delayed_start() {
+        let mut machine = RecordingStateMachine::default();
+        // Shortcut press with the window hidden: the start is delayed.
+        let first = machine.begin_start().unwrap();
+        // A stop and a new start land before the delay runs out.
+        assert_eq!(machine.begin_stop(), Ok(StopRequest::CancelStart));
+        let second = machine.begin_start().unwrap();
+        assert!(second > first);
+
+        // The first delayed emit wakes up and must not start anything.
+        assert!(!machine.confirm_start(first, session()));
+        assert_eq!(machine.phase(), RecordingPhase::Starting);
+        assert!(machine.confirm_start(second, session()));
+        assert_eq!(machine.phase(), RecordingPhase::Recording);
+    }
+
+    #[test]
+    fn stop_before_delayed_start_cancels_it() {
+        let mut machine = RecordingStateMachine::default();
+        let generation = machine.begin_start().unwrap();
+        assert_eq!(machine.begin_stop(), Ok(StopRequest::CancelStart));
+        assert_eq!(machine.phase(), RecordingPhase::Idle);
+
+        assert!(!machine.confirm_start(generation, session()));
+        assert_eq!(machine.phase(), RecordingPhase::Idle);
+        assert!(machine.session.is_none());
+    }
+
+    #[test]
+    fn stop_after_start_hands_back_the_session() {
+        let mut machine = RecordingStateMachine::default();
+        let generation = machine.begin_start().unwrap();
+        let started = session();
+        assert!(machine.confirm_start(generation, started.clone()));
+
+        let Ok(StopRequest::Stop { generation, session }) = machine.begin_stop() else {
+            panic!("expected a stop");
+        };
+        assert_eq!(session, started);
+        assert_eq!(machine.phase(), RecordingPhase::Stopping);
+        assert!(machine.begin_stop().is_err());
+        assert!(machine.confirm_stop(generation));
+        assert_eq!(machine.phase(), RecordingPhase::Idle);
+    }
+
+    #[test]
+    fn generations_only_increase() {
+        let mut machine = RecordingStateMachine::default();
+        let mut last = 0;
+        for _ in 0..3 {
+            let start = machine.begin_start().unwrap();
+            assert!(machine.confirm_start(start, session()));
+            let Ok(StopRequest::Stop { generation, .. }) = machine.begin_stop() else {
+                panic!("expected a stop");
+            };
+            assert!(start > last && generation > start);
+            assert!(machine.confirm_stop(generation));
+            last = generation;
+        }
+    }
+}
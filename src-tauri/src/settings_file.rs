@@ -0,0 +1,148 @@
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Reads `path` as JSON, falling back to the defaults when the file is
+/// missing or unreadable.
+pub fn load_json<T: Default + DeserializeOwned>(path: &Path) -> T {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return T::default();
+    };
+    serde_json::from_str(&contents).unwrap_or_else(|e| {
+        log!("Ignoring unreadable settings file {}: {}", path.display(), e);
+        T::default()
+    })
+}
+
+/// Writes `value` as pretty JSON. The file is written next to `path` and
+/// renamed over it, so a crash mid-write leaves the previous file intact.
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+    temp_name.push(".tmp");
+    let temp = path.with_file_name(temp_name);
+    let mut file = fs::File::create(&temp).map_err(|e| e.to_string())?;
+    file.write_all(json.as_bytes())
+        .and_then(|_| file.sync_all())
+        .map_err(|e| e.to_string())?;
+    fs::rename(&temp, path).map_err(|e| e.to_string())
+}
+
+/// A settings value kept in memory and mirrored to a JSON file in the app
+/// data dir.
+pub struct JsonStore<T> {
+    path: PathBuf,
+    value: Mutex<T>,
+}
+
+impl<T: Default + DeserializeOwned + Serialize + Clone> JsonStore<T> {
+    pub fn load(path: PathBuf) -> Self {
+        let value = load_json(&path);
+        Self {
+            path,
+            value: Mutex::new(value),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.value.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, value: T) -> Result<(), String> {
+        self.update(|current| *current = value)
+    }
+
+    /// Applies `f` to a copy and saves it; the value in memory only changes
+    /// once the file has been written.
+    pub fn update<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R, String> {
+        let mut current = self.lock();
+        let mut updated = current.clone();
+        let result = f(&mut updated);
+        save_json(&self.path, &updated)?;
+        *current = updated;
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+    #[serde(default)]
+    struct Sample {
+        name: String,
+        count: u32,
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asrpro-settings-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = temp_dir("round-trip");
+        let path = dir.join("sample.json");
+        let sample = Sample { name: "a".to_string(), count: 3 };
+        save_json(&path, &sample).unwrap();
+
+        assert_eq!(load_json::<Sample>(&path), sample);
+        // Only the settings file is left behind, not the temp file.
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_or_unreadable_files_load_defaults() {
+        let dir = temp_dir("defaults");
+        let path = dir.join("sample.json");
+        assert_eq!(load_json::<Sample>(&path), Sample::default());
+
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(load_json::<Sample>(&path), Sample::default());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn store_updates_memory_and_disk() {
+        let dir = temp_dir("store");
+        let path = dir.join("sample.json");
+        let store = JsonStore::<Sample>::load(path.clone());
+
+        let count = store.update(|s| {
+            s.count += 2;
+            s.count
+        });
+        assert_eq!(count, Ok(2));
+        store.set(Sample { name: "b".to_string(), count: 5 }).unwrap();
+
+        assert_eq!(store.lock().count, 5);
+        assert_eq!(JsonStore::<Sample>::load(path).lock().name, "b");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn failed_saves_leave_memory_unchanged() {
+        let dir = temp_dir("failed");
+        // A directory where the file should be makes the rename fail.
+        let path = dir.join("sample.json");
+        fs::create_dir_all(path.join("occupied")).unwrap();
+        let store = JsonStore::<Sample>::load(path);
+
+        assert!(store.update(|s| s.count = 9).is_err());
+        assert_eq!(store.lock().count, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard};
+
+use serde::{Deserialize, Serialize};
+use tauri_plugin_global_shortcut::Shortcut;
+
+use crate::settings_file::JsonStore;
+
+pub const DEFAULT_ACCELERATOR: &str = "CommandOrControl+Shift+Space";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutMode {
+    /// Each press starts or stops recording.
+    #[default]
+    Toggle,
+    /// Recording runs only while the shortcut is held down.
+    PushToTalk,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutConfig {
+    pub accelerator: String,
+    pub mode: ShortcutMode,
+}
+
+impl Default for ShortcutConfig {
+    fn default() -> Self {
+        Self {
+            accelerator: DEFAULT_ACCELERATOR.to_string(),
+            mode: ShortcutMode::default(),
+        }
+    }
+}
+
+pub fn parse_accelerator(accelerator: &str) -> Result<Shortcut, String> {
+    accelerator
+        .trim()
+        .parse::<Shortcut>()
+        .map_err(|e| format!("Invalid shortcut '{}': {}", accelerator, e))
+}
+
+pub struct ShortcutSettings {
+    config: JsonStore<ShortcutConfig>,
+    /// The shortcut actually registered with the OS, which differs from the
+    /// saved accelerator when startup had to fall back to the default.
+    registered: Mutex<Option<Shortcut>>,
+}
+
+impl ShortcutSettings {
+    pub fn load(path: PathBuf) -> Self {
+        Self {
+            config: JsonStore::load(path),
+            registered: Mutex::new(None),
+        }
+    }
+
+    pub fn lock(&self) -> MutexGuard<'_, ShortcutConfig> {
+        self.config.lock()
+    }
+
+    pub fn mode(&self) -> ShortcutMode {
+        self.lock().mode
+    }
+
+    pub fn set_mode(&self, mode: ShortcutMode) -> Result<(), String> {
+        self.config.update(|config| config.mode = mode)
+    }
+
+    pub fn set_accelerator(&self, accelerator: String) -> Result<(), String> {
+        self.config.update(|config| config.accelerator = accelerator)
+    }
+
+    pub fn registered(&self) -> Option<Shortcut> {
+        *self.registered.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set_registered(&self, shortcut: Option<Shortcut>) {
+        *self.registered.lock().unwrap_or_else(|e| e.into_inner()) = shortcut;
+    }
+}
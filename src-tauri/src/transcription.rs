@@ -0,0 +1,74 @@
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use uuid::Uuid;
+
+const DEFAULT_BACKEND_URL: &str = "http://localhost:3001";
+const BACKEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Base URL of the transcription backend, overridable with `ASRPRO_BACKEND_URL`.
+pub fn backend_url() -> String {
+    std::env::var("ASRPRO_BACKEND_URL")
+        .ok()
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| DEFAULT_BACKEND_URL.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+pub fn parse_task_id(task_id: &str) -> Result<Uuid, String> {
+    Uuid::parse_str(task_id.trim()).map_err(|e| format!("Invalid task id '{}': {}", task_id, e))
+}
+
+/// Asks the backend to stop the whisper job behind `task_id`.
+pub async fn cancel_on_backend(task_id: &Uuid) -> Result<(), String> {
+    let url = format!("{}/v1/audio/transcriptions/{}", backend_url(), task_id);
+    let response = reqwest::Client::new()
+        .delete(&url)
+        .timeout(BACKEND_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach backend to cancel {}: {}", task_id, e))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(format!(
+        "Backend rejected cancel for {}: HTTP {} {}",
+        task_id,
+        status.as_u16(),
+        body.trim()
+    ))
+}
+
+/// Transcriptions the frontend has reported as in flight, oldest first.
+#[derive(Default)]
+pub struct ActiveTranscriptions(Mutex<Vec<Uuid>>);
+
+impl ActiveTranscriptions {
+    fn lock(&self) -> MutexGuard<'_, Vec<Uuid>> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn start(&self, task_id: Uuid) {
+        let mut active = self.lock();
+        if !active.contains(&task_id) {
+            active.push(task_id);
+        }
+    }
+
+    pub fn finish(&self, task_id: &Uuid) {
+        self.lock().retain(|id| id != task_id);
+    }
+
+    /// The most recently started transcription that is still running.
+    pub fn current(&self) -> Option<Uuid> {
+        self.lock().last().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+}
@@ -0,0 +1,130 @@
+use serde::{Deserialize, Serialize};
+
+use crate::settings_file::JsonStore;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", from = "String")]
+pub enum TrayAction {
+    Nothing,
+    ShowWindow,
+    ToggleRecording,
+    CopyLastTranscript,
+    OpenSettings,
+}
+
+impl From<String> for TrayAction {
+    /// Unknown action names fall back to showing the window.
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "nothing" => TrayAction::Nothing,
+            "show_window" => TrayAction::ShowWindow,
+            "toggle_recording" => TrayAction::ToggleRecording,
+            "copy_last_transcript" => TrayAction::CopyLastTranscript,
+            "open_settings" => TrayAction::OpenSettings,
+            other => {
+                log!("Unknown tray action '{}', falling back to show_window", other);
+                TrayAction::ShowWindow
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrayClick {
+    Left,
+    Double,
+    Middle,
+}
+
+/// Which action each tray click runs. The defaults match the original
+/// hardcoded behaviour: left click shows the window, everything else is ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TrayBehavior {
+    pub left_click: TrayAction,
+    pub double_click: TrayAction,
+    pub middle_click: TrayAction,
+}
+
+impl Default for TrayBehavior {
+    fn default() -> Self {
+        Self {
+            left_click: TrayAction::ShowWindow,
+            double_click: TrayAction::Nothing,
+            middle_click: TrayAction::Nothing,
+        }
+    }
+}
+
+impl TrayBehavior {
+    pub fn action_for(&self, click: TrayClick) -> TrayAction {
+        match click {
+            TrayClick::Left => self.left_click,
+            TrayClick::Double => self.double_click,
+            TrayClick::Middle => self.middle_click,
+        }
+    }
+}
+
+/// Click actions, saved to `tray_behavior.json`.
+pub type TrayBehaviorState = JsonStore<TrayBehavior>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_for_resolves_each_click() {
+        let behavior = TrayBehavior {
+            left_click: TrayAction::ToggleRecording,
+            double_click: TrayAction::OpenSettings,
+            middle_click: TrayAction::CopyLastTranscript,
+        };
+        assert_eq!(behavior.action_for(TrayClick::Left), TrayAction::ToggleRecording);
+        assert_eq!(behavior.action_for(TrayClick::Double), TrayAction::OpenSettings);
+        assert_eq!(behavior.action_for(TrayClick::Middle), TrayAction::CopyLastTranscript);
+    }
+
+    #[test]
+    fn defaults_match_the_original_behaviour() {
+        let behavior = TrayBehavior::default();
+        assert_eq!(behavior.action_for(TrayClick::Left), TrayAction::ShowWindow);
+        assert_eq!(behavior.action_for(TrayClick::Double), TrayAction::Nothing);
+        assert_eq!(behavior.action_for(TrayClick::Middle), TrayAction::Nothing);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = std::env::temp_dir().join(format!("asrpro-tray-{}", std::process::id()));
+        let path = dir.join("tray_behavior.json");
+        let behavior = TrayBehavior {
+            left_click: TrayAction::Nothing,
+            double_click: TrayAction::ShowWindow,
+            middle_click: TrayAction::ToggleRecording,
+        };
+        TrayBehaviorState::load(path.clone()).set(behavior).unwrap();
+        let loaded = *TrayBehaviorState::load(path).lock();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded, behavior);
+    }
+
+    #[test]
+    fn unknown_actions_fall_back_to_show_window() {
+        assert_eq!(TrayAction::from("launch_rockets".to_string()), TrayAction::ShowWindow);
+        let json = r#"{"left_click": "launch_rockets", "middle_click": "open_settings"}"#;
+        let behavior: TrayBehavior = serde_json::from_str(json).unwrap();
+        assert_eq!(behavior.left_click, TrayAction::ShowWindow);
+        assert_eq!(behavior.double_click, TrayAction::Nothing);
+        assert_eq!(behavior.middle_click, TrayAction::OpenSettings);
+    }
+
+    #[test]
+    fn actions_serialize_as_snake_case() {
+        let json = serde_json::to_string(&TrayAction::CopyLastTranscript).unwrap();
+        assert_eq!(json, r#""copy_last_transcript""#);
+        assert_eq!(
+            serde_json::from_str::<TrayAction>(&json).unwrap(),
+            TrayAction::CopyLastTranscript
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use tauri::image::Image;
+use tauri::{AppHandle, Manager};
+
+/// Same file as the tray's `iconPath` in tauri.conf.json, so restoring it
+/// after a recording doesn't change the icon.
+pub const IDLE_ICON: Image<'static> = tauri::include_image!("./icons/icon.png");
+pub const IDLE_TOOLTIP: &str = "ASR Pro";
+const RECORDING_DOT: [u8; 4] = [229, 72, 77, 255];
+
+/// Returns a copy of `base` with a red dot in the bottom-right corner.
+pub fn recording_icon(base: &Image<'_>) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+    let radius = (width.min(height) as f32) / 4.0;
+    let center_x = width as f32 - radius - 1.0;
+    let center_y = height as f32 - radius - 1.0;
+
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - center_x;
+            let dy = y as f32 + 0.5 - center_y;
+            if dx * dx + dy * dy <= radius * radius {
+                let offset = ((y * width + x) * 4) as usize;
+                rgba[offset..offset + 4].copy_from_slice(&RECORDING_DOT);
+            }
+        }
+    }
+
+    Image::new_owned(rgba, width, height)
+}
+
+/// Formats an elapsed recording time as `MM:SS`, or `H:MM:SS` past an hour.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, (secs % 3600) / 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{:02}:{:02}", minutes, seconds)
+    }
+}
+
+/// Counts recording starts and stops: odd while recording, even when idle. A
+/// ticker remembers the generation it was started in and stops once it moves
+/// on, without taking a lock around tray calls (they block on the main thread).
+#[derive(Default)]
+struct Generation(AtomicU64);
+
+impl Generation {
+    fn begin(&self) -> u64 {
+        let step = |current: u64| current + if current % 2 == 0 { 1 } else { 2 };
+        let previous = self
+            .0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| Some(step(current)))
+            .unwrap_or_else(|current| current);
+        step(previous)
+    }
+
+    fn end(&self) {
+        let _ = self
+            .0
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |current| {
+                (current % 2 == 1).then_some(current + 1)
+            });
+    }
+
+    fn is_current(&self, generation: u64) -> bool {
+        self.0.load(Ordering::SeqCst) == generation
+    }
+
+    fn is_idle(&self) -> bool {
+        self.0.load(Ordering::SeqCst) % 2 == 0
+    }
+}
+
+/// Swaps the tray icon while recording and keeps the tooltip's elapsed time
+/// current from a background thread that stops with the recording.
+#[derive(Default)]
+pub struct RecordingIndicator {
+    generation: Generation,
+}
+
+impl RecordingIndicator {
+    pub fn start(&self, app: &AppHandle) {
+        let generation = self.generation.begin();
+
+        if let Some(tray) = app.tray_by_id(crate::TRAY_ID) {
+            let _ = tray.set_icon_as_template(false);
+            let _ = tray.set_icon(Some(recording_icon(&IDLE_ICON)));
+        }
+
+        let app = app.clone();
+        let started = Instant::now();
+        std::thread::spawn(move || loop {
+            let indicator = app.state::<RecordingIndicator>();
+            if !indicator.generation.is_current(generation) {
+                break;
+            }
+            if let Some(tray) = app.tray_by_id(crate::TRAY_ID) {
+                let elapsed = format_elapsed(started.elapsed());
+                let tooltip = format!("{} — recording {}", IDLE_TOOLTIP, elapsed);
+                let _ = tray.set_tooltip(Some(tooltip));
+                // `stop` may have reset the tooltip while ours was in flight;
+                // put the idle one back so the stale time doesn't stick.
+                if !indicator.generation.is_current(generation) {
+                    if indicator.generation.is_idle() {
+                        let _ = tray.set_tooltip(Some(IDLE_TOOLTIP));
+                    }
+                    break;
+                }
+            }
+            std::thread::sleep(Duration::from_secs(1));
+        });
+    }
+
+    pub fn stop(&self, app: &AppHandle) {
+        self.generation.end();
+
+        if let Some(tray) = app.tray_by_id(crate::TRAY_ID) {
+            let _ = tray.set_icon(Some(IDLE_ICON));
+            let _ = tray.set_icon_as_template(true);
+            let _ = tray.set_tooltip(Some(IDLE_TOOLTIP));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stopping_retires_the_recording_generation() {
+        let generation = Generation::default();
+        assert!(generation.is_idle());
+
+        let first = generation.begin();
+        assert!(generation.is_current(first));
+        assert!(!generation.is_idle());
+
+        generation.end();
+        assert!(!generation.is_current(first));
+        assert!(generation.is_idle());
+    }
+
+    #[test]
+    fn restarting_retires_the_previous_ticker() {
+        let generation = Generation::default();
+        let first = generation.begin();
+        let second = generation.begin();
+        assert_ne!(first, second);
+        assert!(!generation.is_current(first));
+        assert!(generation.is_current(second));
+        assert!(!generation.is_idle());
+    }
+
+    #[test]
+    fn stopping_twice_stays_idle() {
+        let generation = Generation::default();
+        let first = generation.begin();
+        generation.end();
+        generation.end();
+        assert!(generation.is_idle());
+        assert!(generation.begin() > first);
+    }
+
+    #[test]
+    fn format_elapsed_adds_hours_when_needed() {
+        assert_eq!(format_elapsed(Duration::from_secs(65)), "01:05");
+        assert_eq!(format_elapsed(Duration::from_secs(3_725)), "1:02:05");
+    }
+}
@@ -1,4 +1,5 @@
 import { useState, useEffect, useId } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { usePlatform } from "./services/platform";
 import { useAccessibility } from "./services/accessibility";
@@ -11,6 +12,7 @@ import SystemTray from "./components/SystemTray";
 import ToastContainer from "./components/ToastContainer";
 import RecordingOverlay from "./components/RecordingOverlay";
 import TrayNotificationList from "./components/TrayNotificationList";
+import MicPermissionNotice from "./components/MicPermissionNotice";
 
 // PalantirUI Components - removed unused imports
 
@@ -18,6 +20,7 @@ import TrayNotificationList from "./components/TrayNotificationList";
 import { useWindowState } from "./hooks/useWindowState";
 import { useRecording } from "./services/recordingManager";
 import { useTrayNotifications } from "./services/trayNotifications";
+import { toastService, ToastType } from "./services/toast";
 import { useWebSocket } from "./hooks/useWebSocket";
 import { globalShortcutService } from "./services/globalShortcut";
 
@@ -26,6 +29,8 @@ function App() {
   const [activeSection, setActiveSection] = useState("general");
   const [isRecordingOverlayActive, setIsRecordingOverlayActive] =
     useState(false);
+  // Files opened from the command line, waiting for the Transcribe page
+  const [openedPaths, setOpenedPaths] = useState<string[]>([]);
   const platformInfo = usePlatform();
   useAccessibility(); // Initialize accessibility features
   // Window state management is handled internally
@@ -35,7 +40,7 @@ function App() {
   const { state: recordingState } = useRecording();
 
   // Tray notification state
-  const { notifications } = useTrayNotifications();
+  const { notifications, addNotification } = useTrayNotifications();
 
   // WebSocket connection (for future use)
   useWebSocket();
@@ -64,6 +69,88 @@ function App() {
     };
   }, []);
 
+  // Let the tray jump straight to a settings section
+  useEffect(() => {
+    const unlistenNavigate = listen<{ section: string }>("navigate", (event) => {
+      setActiveSection(event.payload.section);
+    });
+
+    return () => {
+      unlistenNavigate.then((fn) => fn());
+    };
+  }, []);
+
+  // Notifications Rust showed in-app instead of natively (window visible,
+  // do-not-disturb, or the native call failed). These go straight to the
+  // toast list, never back through show_tray_notification.
+  useEffect(() => {
+    const unlistenNotification = listen<{
+      id: string | null;
+      title: string;
+      message: string;
+      type: string;
+      progress: number | null;
+    }>("show-notification", (event) => {
+      const { id, title, message, type, progress } = event.payload;
+      const toastType = ["success", "warning", "error"].includes(type)
+        ? (type as ToastType)
+        : "info";
+      // Updates with the same id replace one toast, which stays up until
+      // its progress completes
+      const inProgress = progress !== null && progress < 1;
+      toastService.show({
+        id: id ?? undefined,
+        type: toastType,
+        title,
+        message: message || undefined,
+        progress: inProgress ? progress : undefined,
+        persistent: inProgress,
+      });
+    });
+
+    return () => {
+      unlistenNotification.then((fn) => fn());
+    };
+  }, []);
+
+  // Files passed on the command line, either at startup or by a second launch
+  useEffect(() => {
+    const openFiles = (paths: string[]) => {
+      if (paths.length === 0) return;
+      setOpenedPaths((current) => [...current, ...paths]);
+      setActiveSection("transcribe");
+    };
+
+    invoke<string[]>("take_pending_files").then(openFiles).catch((error) => {
+      console.error("Failed to read pending files:", error);
+    });
+    const unlistenOpenFiles = listen<{ paths: string[] }>("open-files", (event) => {
+      openFiles(event.payload.paths);
+    });
+
+    return () => {
+      unlistenOpenFiles.then((fn) => fn());
+    };
+  }, []);
+
+  // Confirm clipboard output from copy_to_clipboard
+  useEffect(() => {
+    const unlistenCopied = listen<{ length: number; autoPasted: boolean }>(
+      "clipboard-copied",
+      (event) => {
+        addNotification(
+          "success",
+          "Copied to clipboard",
+          event.payload.autoPasted ? "Pasted into the active window" : undefined
+        );
+      }
+    );
+
+    return () => {
+      unlistenCopied.then((fn) => fn());
+    };
+  }, []);
+
   // Listen for global shortcut events to control recording overlay
   useEffect(() => {
     const unlistenStart = listen("recording-start", () => {
@@ -97,6 +184,7 @@ function App() {
         isTranscribing={recordingState.isTranscribing}
         statusText={recordingState.statusText}
       />
+      <MicPermissionNotice />
       {notifications.length > 0 && <TrayNotificationList />}
       
       <div className="flex h-full" id={mainContentId}>
@@ -106,6 +194,8 @@ function App() {
         />
         <ContentArea
           activeSection={activeSection}
+          openedPaths={openedPaths}
+          onOpenedPathsTaken={() => setOpenedPaths([])}
         />
       </div>
     </div>
@@ -14,10 +14,14 @@ import { PalPanel, PalPanelContent } from "./palantirui";
 
 interface ContentAreaProps {
   activeSection: string;
+  openedPaths?: string[];
+  onOpenedPathsTaken?: () => void;
 }
 
 const ContentArea: React.FC<ContentAreaProps> = ({
   activeSection,
+  openedPaths,
+  onOpenedPathsTaken,
 }) => {
   const renderPage = () => {
     switch (activeSection) {
@@ -30,7 +34,12 @@ const ContentArea: React.FC<ContentAreaProps> = ({
       case "keyboard":
         return <KeyboardPage />;
       case "transcribe":
-        return <TranscribePage />;
+        return (
+          <TranscribePage
+            openedPaths={openedPaths}
+            onOpenedPathsTaken={onOpenedPathsTaken}
+          />
+        );
       case "accessibility":
         return <AccessibilityPage />;
       case "about":
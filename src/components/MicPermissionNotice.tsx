@@ -0,0 +1,112 @@
+import React, { useState } from "react";
+import { invoke } from "@tauri-apps/api/core";
+import { Mic, MicOff } from "lucide-react";
+import { useMicPermission, MicStatus } from "../services/micPermission";
+import { usePlatform } from "../services/platform";
+import { PalModal, PalModalHeader, PalModalContent, PalModalFooter, PalButton } from "./palantirui";
+
+const blockedMessage = (status: MicStatus, isLinux: boolean): string => {
+  if (status.declined) {
+    return "You chose not to let ASR Pro use the microphone. Allow it to start recording.";
+  }
+  if (status.access === "unavailable") {
+    return "No audio server was found. Make sure PipeWire or PulseAudio is running and a microphone is connected, then try again.";
+  }
+  if (isLinux) {
+    return "The microphone was blocked. Allow microphone access for ASR Pro in your desktop's privacy settings (or the portal prompt), then try again.";
+  }
+  return "The microphone was blocked. Allow ASR Pro in your system's microphone privacy settings, then try again.";
+};
+
+const MicPermissionNotice: React.FC = () => {
+  const { status, explanationRequested, setConsent, retry, openSystemSettings } = useMicPermission();
+  const platformInfo = usePlatform();
+  const [isChecking, setIsChecking] = useState(false);
+
+  const run = async (action: () => Promise<unknown>) => {
+    setIsChecking(true);
+    try {
+      await action();
+    } catch (error) {
+      console.error("Microphone permission action failed:", error);
+    } finally {
+      setIsChecking(false);
+    }
+  };
+
+  // Allowing from the dialog goes straight on to the recording the user asked for
+  const handleAllow = () => run(async () => {
+    const updated = await setConsent(true);
+    if (updated.can_record) {
+      await invoke("start_recording").catch(() => {});
+    }
+  });
+
+  const handleTryAgain = () => run(async () => {
+    if (status?.declined) {
+      await setConsent(true);
+    }
+    await retry();
+  });
+
+  const canOpenSettings = platformInfo.isMacOS() || platformInfo.isWindows();
+  const isBlocked = status !== null && !status.can_record && !status.needs_explanation;
+
+  return (
+    <>
+      <PalModal
+        isOpen={explanationRequested}
+        onClose={() => run(() => setConsent(false))}
+        size="sm"
+        withCornerMarkers={true}
+        closeOnBackdropClick={false}
+      >
+        <PalModalHeader
+          title="Use your microphone?"
+          subtitle="ASR Pro needs it to record dictation"
+        />
+        <PalModalContent>
+          <p className="text-sm text-palantir-zinc-600 dark:text-palantir-zinc-400">
+            Audio is only captured while the recording window is open and is sent to
+            your transcription backend. Your system may ask for permission next.
+          </p>
+        </PalModalContent>
+        <PalModalFooter>
+          <div className="flex justify-end gap-3 w-full">
+            <PalButton variant="ghost" onClick={() => run(() => setConsent(false))} disabled={isChecking}>
+              Not now
+            </PalButton>
+            <PalButton variant="primary" onClick={handleAllow} disabled={isChecking} className="flex items-center gap-2">
+              <Mic size={16} />
+              <span>Allow</span>
+            </PalButton>
+          </div>
+        </PalModalFooter>
+      </PalModal>
+
+      {isBlocked && status && (
+        <div
+          role="alert"
+          className="fixed bottom-4 left-1/2 -translate-x-1/2 z-40 flex items-center gap-4 max-w-2xl px-4 py-3 rounded-pal border border-palantir-accent-orange bg-palantir-zinc-50 dark:bg-palantir-zinc-900 shadow-lg"
+        >
+          <MicOff size={20} className="shrink-0 text-palantir-accent-orange" />
+          <p className="text-sm text-palantir-zinc-700 dark:text-palantir-zinc-300">
+            {blockedMessage(status, platformInfo.isLinux())}
+          </p>
+          <div className="flex shrink-0 gap-2">
+            {canOpenSettings && status.access === "denied" && !status.declined && (
+              <PalButton variant="ghost" onClick={() => run(openSystemSettings)} disabled={isChecking}>
+                Open settings
+              </PalButton>
+            )}
+            <PalButton variant="primary" onClick={handleTryAgain} disabled={isChecking}>
+              {status.declined ? "Allow microphone" : "Try again"}
+            </PalButton>
+          </div>
+        </div>
+      )}
+    </>
+  );
+};
+
+export default MicPermissionNotice;
@@ -1,8 +1,10 @@
 import React, { useEffect, useState } from "react";
 import { Mic, MicOff, Square, Loader2 } from "lucide-react";
+import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { useAudioRecording } from "../hooks/useAudioRecording";
 import { useRecording } from "../services/recordingManager";
+import { micPermissionManager } from "../services/micPermission";
 import { PalModal, PalModalHeader, PalModalContent, PalModalFooter, PalButton } from "./palantirui";
 
 export interface RecordingOverlayProps {
@@ -50,8 +52,10 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
       }
     });
 
-    const unlistenStop = listen("recording-stop", () => {
-      if (isActive) {
+    const unlistenStop = listen<{ cancelled?: boolean }>("recording-stop", (event) => {
+      if (event.payload?.cancelled) {
+        handleCancel();
+      } else if (isActive) {
         handleStop();
       }
     });
@@ -78,12 +82,12 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
       switch (event.key) {
         case "Escape":
           event.preventDefault();
-          handleCancel();
+          requestStop(true);
           break;
         case " ":
           event.preventDefault();
           if (isTranscribing || isTranscribingLocal) {
-            handleStop();
+            requestStop();
           } else {
             handleStart();
           }
@@ -104,6 +108,20 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
   }, [isActive, isTranscribing, isTranscribingLocal]);
 
   const handleStart = async () => {
+    try {
+      const mic = micPermissionManager.getState().status ?? await micPermissionManager.refresh();
+      if (!mic.can_record) {
+        // The consent dialog or the permission banner takes it from here.
+        if (mic.needs_explanation) {
+          micPermissionManager.requestExplanation();
+        }
+        await invoke("stop_recording", { cancelled: true }).catch(() => {});
+        return;
+      }
+    } catch (error) {
+      console.error("Failed to read microphone status:", error);
+    }
+
     try {
       await startRecording({
         sampleRate: 16000,
@@ -112,8 +130,11 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
         noiseSuppression: true,
       });
       start();
+      await micPermissionManager.reportCapture().catch(() => null);
     } catch (error) {
       console.error("Failed to start recording:", error);
+      await micPermissionManager.reportCapture(error).catch(() => null);
+      await invoke("stop_recording", { cancelled: true }).catch(() => {});
     }
   };
 
@@ -145,6 +166,22 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
     setIsTranscribingLocal(false);
   };
 
+  // Stops go through Rust so its recording state machine stays in sync; the
+  // recording-stop event it emits then runs handleStop/handleCancel above.
+  const requestStop = async (cancelled = false) => {
+    try {
+      await invoke("stop_recording", { cancelled });
+    } catch (error) {
+      // Rust had no session (the overlay started this recording itself).
+      console.warn("stop_recording rejected, stopping locally:", error);
+      if (cancelled) {
+        handleCancel();
+      } else {
+        handleStop();
+      }
+    }
+  };
+
   const formatDuration = (seconds: number): string => {
     const mins = Math.floor(seconds / 60);
     const secs = seconds % 60;
@@ -162,7 +199,7 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
   return (
     <PalModal
       isOpen={isRecording}
-      onClose={handleCancel}
+      onClose={() => requestStop(true)}
       size="md"
       withGlow={true}
       withCornerMarkers={true}
@@ -226,7 +263,7 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
             {isTranscribingFinal ? (
               <PalButton
                 variant="primary"
-                onClick={handleStop}
+                onClick={() => requestStop()}
                 withGlow={true}
                 withCornerMarkers={true}
                 className="flex items-center gap-2"
@@ -237,7 +274,7 @@ const RecordingOverlay: React.FC<RecordingOverlayProps> = ({
             ) : (
               <PalButton
                 variant="secondary"
-                onClick={handleCancel}
+                onClick={() => requestStop(true)}
                 withGlow={true}
                 withCornerMarkers={true}
                 className="flex items-center gap-2"
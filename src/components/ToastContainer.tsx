@@ -72,6 +72,21 @@ const ToastContainer: React.FC = () => {
             </PalButton>
           </div>
 
+          {toast.progress !== undefined && (
+            <div
+              className="w-full bg-palantir-zinc-200 dark:bg-palantir-zinc-700 rounded-full h-1.5 mt-3"
+              role="progressbar"
+              aria-valuenow={Math.round(toast.progress * 100)}
+              aria-valuemin={0}
+              aria-valuemax={100}
+            >
+              <div
+                className="bg-palantir-accent-blue h-1.5 rounded-full transition-all duration-300"
+                style={{ width: `${Math.min(Math.max(toast.progress, 0), 1) * 100}%` }}
+              />
+            </div>
+          )}
+
           {toast.actions && toast.actions.length > 0 && (
             <div className="flex space-x-2 mt-3 pt-3 border-t border-palantir-zinc-200 dark:border-palantir-zinc-700">
               {toast.actions.map((action, actionIndex) => (
@@ -1,10 +1,73 @@
-import React from 'react';
+import React, { useEffect, useState } from 'react';
+import { invoke } from '@tauri-apps/api/core';
 import { useSettings } from '../services/settings';
-import { PalPanelHeader, PalText, PalCard } from '../components/palantirui';
+import { PalPanelHeader, PalText, PalCard, PalSelect } from '../components/palantirui';
 import { TacticalToggle } from '../components/TacticalToggle';
 
+type TrayAction =
+  | 'nothing'
+  | 'show_window'
+  | 'toggle_recording'
+  | 'copy_last_transcript'
+  | 'open_settings';
+
+interface TrayBehavior {
+  left_click: TrayAction;
+  double_click: TrayAction;
+  middle_click: TrayAction;
+}
+
+interface NotificationSettings {
+  webview_fallback: boolean;
+}
+
+const TRAY_ACTION_OPTIONS: { id: TrayAction; name: string }[] = [
+  { id: 'nothing', name: 'Do nothing' },
+  { id: 'show_window', name: 'Show window' },
+  { id: 'toggle_recording', name: 'Toggle recording' },
+  { id: 'copy_last_transcript', name: 'Copy last transcript' },
+  { id: 'open_settings', name: 'Open settings' },
+];
+
+const TRAY_CLICKS: { key: keyof TrayBehavior; label: string; description: string }[] = [
+  { key: 'left_click', label: 'Left Click', description: 'Action when the tray icon is clicked' },
+  { key: 'double_click', label: 'Double Click', description: 'Action when the tray icon is double-clicked' },
+  { key: 'middle_click', label: 'Middle Click', description: 'Action when the tray icon is middle-clicked' },
+];
+
 const GeneralPage: React.FC = () => {
   const { settings, updateSetting } = useSettings();
+  const [trayBehavior, setTrayBehavior] = useState<TrayBehavior | null>(null);
+  const [notificationSettings, setNotificationSettings] = useState<NotificationSettings | null>(null);
+
+  useEffect(() => {
+    invoke<TrayBehavior>('get_tray_behavior')
+      .then(setTrayBehavior)
+      .catch((error) => console.error('Failed to load tray behavior:', error));
+    invoke<NotificationSettings>('get_notification_settings')
+      .then(setNotificationSettings)
+      .catch((error) => console.error('Failed to load notification settings:', error));
+  }, []);
+
+  const updateNotificationFallback = async (enabled: boolean) => {
+    try {
+      await invoke('set_notification_fallback', { enabled });
+      setNotificationSettings({ webview_fallback: enabled });
+    } catch (error) {
+      console.error('Failed to save notification settings:', error);
+    }
+  };
+
+  const updateTrayAction = async (key: keyof TrayBehavior, action: TrayAction) => {
+    if (!trayBehavior) return;
+    const behavior = { ...trayBehavior, [key]: action };
+    try {
+      await invoke('set_tray_behavior', { behavior });
+      setTrayBehavior(behavior);
+    } catch (error) {
+      console.error('Failed to save tray behavior:', error);
+    }
+  };
 
   return (
     <div className="pal-section pal-container pal-card-spacing">
@@ -69,6 +132,72 @@ const GeneralPage: React.FC = () => {
         </div>
       </PalCard>
 
+      {trayBehavior && (
+        <PalCard
+          variant="default"
+          padding="lg"
+          withGlow={true}
+          withCornerMarkers={true}
+          className="pal-p-lg"
+        >
+          <div className="pal-card-spacing">
+            <PalText size="lg" weight="semibold" className="pal-mb-lg">Tray Icon</PalText>
+
+            {TRAY_CLICKS.map(({ key, label, description }, index) => (
+              <div
+                key={key}
+                className={`pal-form-row pal-p-md${index < TRAY_CLICKS.length - 1 ? ' border-b' : ''}`}
+              >
+                <div>
+                  <PalText weight="medium">{label}</PalText>
+                  <PalText size="sm" variant="muted">
+                    {description}
+                  </PalText>
+                </div>
+                <PalSelect
+                  value={trayBehavior[key]}
+                  onChange={(e) => updateTrayAction(key, e.target.value as TrayAction)}
+                >
+                  {TRAY_ACTION_OPTIONS.map((option) => (
+                    <option key={option.id} value={option.id}>
+                      {option.name}
+                    </option>
+                  ))}
+                </PalSelect>
+              </div>
+            ))}
+          </div>
+        </PalCard>
+      )}
+
+      {notificationSettings && (
+        <PalCard
+          variant="default"
+          padding="lg"
+          withGlow={true}
+          withCornerMarkers={true}
+          className="pal-p-lg"
+        >
+          <div className="pal-card-spacing">
+            <PalText size="lg" weight="semibold" className="pal-mb-lg">Notifications</PalText>
+
+            <div className="pal-form-row pal-p-md">
+              <div>
+                <PalText weight="medium">In-app Fallback</PalText>
+                <PalText size="sm" variant="muted">
+                  Show notifications inside ASR Pro when desktop notifications fail
+                </PalText>
+              </div>
+              <TacticalToggle
+                checked={notificationSettings.webview_fallback}
+                onChange={updateNotificationFallback}
+                size="md"
+              />
+            </div>
+          </div>
+        </PalCard>
+      )}
+
       <PalCard
         variant="default"
         padding="lg"
@@ -1,10 +1,22 @@
-import { useState, useId, type FC } from "react";
+import { useState, useEffect, useId, type FC } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { Upload, FileAudio, FolderOpen, Container, Clock } from "lucide-react";
 import { fileSystemService } from "../services/fileSystem";
 import { apiClient, TranscriptionResponse } from "../services/api";
 import { PalPanelHeader, PalText, PalCard, PalButton, PalSelect } from "../components/palantirui";
 
-const TranscribePage: FC = () => {
+interface TranscribePageProps {
+  // Files opened from the command line; handed to processFiles once read
+  openedPaths?: string[];
+  onOpenedPathsTaken?: () => void;
+}
+
+const toAudioFile = (path: string, data: ArrayBuffer): File => {
+  const fileName = path.split(/[\\/]/).pop() || "unknown";
+  return new File([data], fileName, { type: "audio/wav" });
+};
+
+const TranscribePage: FC<TranscribePageProps> = ({ openedPaths = [], onOpenedPathsTaken }) => {
   const fileInputId = useId();
   const [isDragging, setIsDragging] = useState(false);
   const [isProcessing, setIsProcessing] = useState(false);
@@ -70,6 +82,29 @@ const TranscribePage: FC = () => {
     }
   };
 
+  // Transcribe files opened from the command line once nothing else is running
+  useEffect(() => {
+    if (openedPaths.length === 0 || isProcessing) return;
+    onOpenedPathsTaken?.();
+
+    const openFiles = async () => {
+      const results = await Promise.allSettled(
+        openedPaths.map(async (path) =>
+          toAudioFile(path, await invoke<ArrayBuffer>("read_opened_file", { path }))
+        )
+      );
+      const files = results.flatMap((result, index) => {
+        if (result.status === "fulfilled") return [result.value];
+        console.error(`Failed to read ${openedPaths[index]}:`, result.reason);
+        return [];
+      });
+      if (files.length > 0) {
+        processFiles(files);
+      }
+    };
+    openFiles();
+  }, [openedPaths, isProcessing]);
+
   const processFiles = async (files: File[]) => {
     setIsProcessing(true);
     setCurrentFile(files[0].name);
@@ -93,7 +93,13 @@ class ApiClient {
         });
     }
 
-    async transcribeFile(file: File, model?: string, responseFormat: 'json' | 'text' | 'srt' = 'json'): Promise<any> {
+    async transcribeFile(
+        file: File,
+        model?: string,
+        responseFormat: 'json' | 'text' | 'srt' = 'json',
+        taskId?: string,
+        signal?: AbortSignal,
+    ): Promise<any> {
         const formData = new FormData();
         formData.append('file', file);
 
@@ -103,9 +109,11 @@ class ApiClient {
 
         formData.append('response_format', responseFormat);
 
-        const response = await fetch(`${this.baseUrl}/v1/audio/transcriptions`, {
+        const query = taskId ? `?task_id=${encodeURIComponent(taskId)}` : '';
+        const response = await fetch(`${this.baseUrl}/v1/audio/transcriptions${query}`, {
             method: 'POST',
             body: formData,
+            signal,
         });
 
         if (!response.ok) {
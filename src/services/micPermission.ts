@@ -0,0 +1,161 @@
+// Microphone consent and access for ASR Pro
+import { invoke, isTauri } from '@tauri-apps/api/core';
+
+export type MicAccess = 'granted' | 'denied' | 'unavailable' | 'unknown';
+
+export interface MicStatus {
+  needs_explanation: boolean;
+  declined: boolean;
+  access: MicAccess;
+  can_record: boolean;
+}
+
+export interface MicPermissionState {
+  status: MicStatus | null;
+  explanationRequested: boolean;
+}
+
+// Outside the desktop app the browser's own prompt is all there is
+const BROWSER_STATUS: MicStatus = {
+  needs_explanation: false,
+  declined: false,
+  access: 'unknown',
+  can_record: true,
+};
+
+class MicPermissionManager {
+  private state: MicPermissionState = {
+    status: null,
+    explanationRequested: false,
+  };
+
+  private listeners: ((state: MicPermissionState) => void)[] = [];
+
+  /**
+   * Subscribe to permission changes
+   */
+  subscribe(listener: (state: MicPermissionState) => void): () => void {
+    this.listeners.push(listener);
+    return () => {
+      const index = this.listeners.indexOf(listener);
+      if (index > -1) {
+        this.listeners.splice(index, 1);
+      }
+    };
+  }
+
+  private notifyListeners(): void {
+    this.listeners.forEach(listener => listener({ ...this.state }));
+  }
+
+  private setState(updates: Partial<MicPermissionState>): void {
+    this.state = { ...this.state, ...updates };
+    this.notifyListeners();
+  }
+
+  private setStatus(status: MicStatus): MicStatus {
+    this.setState({ status });
+    return status;
+  }
+
+  /**
+   * Fetch the current status; `recheck` probes the system again instead of
+   * trusting an earlier denial
+   */
+  async refresh(recheck = false): Promise<MicStatus> {
+    if (!isTauri()) {
+      return this.setStatus(BROWSER_STATUS);
+    }
+    return this.setStatus(await invoke<MicStatus>('get_microphone_status', { recheck }));
+  }
+
+  /**
+   * Ask the user to read the explanation before the first capture attempt
+   */
+  requestExplanation(): void {
+    this.setState({ explanationRequested: true });
+  }
+
+  /**
+   * Save the answer to the explanation
+   */
+  async setConsent(allowed: boolean): Promise<MicStatus> {
+    this.setState({ explanationRequested: false });
+    if (!isTauri()) {
+      return this.setStatus(BROWSER_STATUS);
+    }
+    return this.setStatus(await invoke<MicStatus>('set_microphone_consent', { allowed }));
+  }
+
+  /**
+   * Tell the Rust side how a capture attempt went. Errors other than a
+   * refusal (busy device, aborted request) say nothing about permission.
+   */
+  async reportCapture(error?: unknown): Promise<MicStatus | null> {
+    const refused = error instanceof DOMException &&
+      (error.name === 'NotAllowedError' || error.name === 'SecurityError');
+    if (error !== undefined && !refused) {
+      return this.state.status;
+    }
+    if (!isTauri()) {
+      return this.state.status;
+    }
+    return this.setStatus(await invoke<MicStatus>('report_microphone_access', { granted: !refused }));
+  }
+
+  /**
+   * Probe again and, if nothing rules it out, open the microphone briefly so
+   * the OS decides whether the user fixed their settings
+   */
+  async retry(): Promise<MicStatus> {
+    const status = await this.refresh(true);
+    if (!status.can_record) {
+      return status;
+    }
+    try {
+      const stream = await navigator.mediaDevices.getUserMedia({ audio: true });
+      stream.getTracks().forEach(track => track.stop());
+      return (await this.reportCapture()) ?? status;
+    } catch (error) {
+      return (await this.reportCapture(error)) ?? status;
+    }
+  }
+
+  /**
+   * Open the OS microphone privacy settings (macOS and Windows only)
+   */
+  async openSystemSettings(): Promise<void> {
+    await invoke('open_microphone_settings');
+  }
+
+  getState(): MicPermissionState {
+    return { ...this.state };
+  }
+}
+
+// Export singleton instance
+export const micPermissionManager = new MicPermissionManager();
+
+// Export hook for React components
+export const useMicPermission = () => {
+  const [state, setState] = React.useState<MicPermissionState>(micPermissionManager.getState());
+
+  React.useEffect(() => {
+    const unsubscribe = micPermissionManager.subscribe(setState);
+    micPermissionManager.refresh().catch(error => {
+      console.error('Failed to read microphone status:', error);
+    });
+    return unsubscribe;
+  }, []);
+
+  return {
+    ...state,
+    refresh: micPermissionManager.refresh.bind(micPermissionManager),
+    setConsent: micPermissionManager.setConsent.bind(micPermissionManager),
+    retry: micPermissionManager.retry.bind(micPermissionManager),
+    openSystemSettings: micPermissionManager.openSystemSettings.bind(micPermissionManager),
+  };
+};
+
+// Import React for the hook
+import React from 'react';
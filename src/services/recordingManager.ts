@@ -1,4 +1,5 @@
 // Recording state management for ASR Pro
+import { invoke, isTauri } from '@tauri-apps/api/core';
 import { apiClient } from './api';
 
 export interface RecordingState {
@@ -31,6 +32,8 @@ class RecordingManager {
 
   private listeners: ((state: RecordingState) => void)[] = [];
   private intervalId: number | null = null;
+  private activeTasks = new Map<string, AbortController>();
+  private cancelListener: Promise<void> | null = null;
 
   /**
    * Subscribe to recording state changes
@@ -200,21 +203,83 @@ class RecordingManager {
    * Transcribe a recorded audio file
    */
   async transcribeFile(audioBlob: Blob): Promise<string> {
+    const taskId = crypto.randomUUID();
+    const controller = new AbortController();
+    this.activeTasks.set(taskId, controller);
+
     try {
       this.startTranscribing();
+      await this.reportTask('transcription_started', taskId);
 
       // Convert blob to file
       const audioFile = new File([audioBlob], 'recording.wav', { type: 'audio/wav' });
 
       // Use the API client to transcribe
-      const result = await apiClient.transcribeFile(audioFile);
+      const result = await apiClient.transcribeFile(audioFile, undefined, 'json', taskId, controller.signal);
 
       this.completeTranscription();
 
+      if (result.text) {
+        this.rememberTranscript(result.text);
+      }
+
       return result.text || 'No transcription result';
     } catch (error) {
-      this.error(`Transcription failed: ${error instanceof Error ? error.message : 'Unknown error'}`);
+      if (controller.signal.aborted) {
+        this.cancel();
+      } else {
+        this.error(`Transcription failed: ${error instanceof Error ? error.message : 'Unknown error'}`);
+      }
       throw error;
+    } finally {
+      this.activeTasks.delete(taskId);
+      await this.reportTask('transcription_finished', taskId);
+    }
+  }
+
+  /**
+   * Tell the Rust side a transcription started or finished, so the tray can
+   * offer to cancel it
+   */
+  private async reportTask(command: 'transcription_started' | 'transcription_finished', taskId: string): Promise<void> {
+    try {
+      if (isTauri()) {
+        if (!this.cancelListener) {
+          this.cancelListener = this.listenForCancel();
+        }
+        await this.cancelListener;
+        await invoke(command, { taskId });
+      }
+    } catch (error) {
+      console.error(`Failed to report ${command}:`, error);
+    }
+  }
+
+  /**
+   * Abort the matching request when the tray cancels a transcription
+   */
+  private async listenForCancel(): Promise<void> {
+    const { listen } = await import('@tauri-apps/api/event');
+
+    await listen<{ task_id: string }>('transcription-cancel', (event) => {
+      this.activeTasks.get(event.payload.task_id)?.abort();
+    });
+  }
+
+  /**
+   * Add a finished transcription to the tray's recent transcripts
+   */
+  private async rememberTranscript(text: string): Promise<void> {
+    try {
+      if (isTauri()) {
+        await invoke('record_completed_transcript', {
+          id: crypto.randomUUID(),
+          title: text.trim().split('\n')[0],
+          text,
+        });
+      }
+    } catch (error) {
+      console.error('Failed to record transcript:', error);
     }
   }
 
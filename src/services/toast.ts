@@ -8,6 +8,7 @@ export interface Toast {
   message?: string;
   duration?: number; // in milliseconds
   persistent?: boolean; // won't auto-dismiss
+  progress?: number; // 0..1, shown as a bar
   actions?: ToastAction[];
 }
 
@@ -20,6 +21,7 @@ export interface ToastAction {
 class ToastService {
   private toasts: Toast[] = [];
   private listeners: ((toasts: Toast[]) => void)[] = [];
+  private timers = new Map<string, ReturnType<typeof setTimeout>>();
 
   /**
    * Subscribe to toast changes
@@ -72,7 +74,8 @@ class ToastService {
   }
 
   /**
-   * Show a toast with custom options
+   * Show a toast with custom options. Showing an id that is already on
+   * screen updates that toast in place.
    */
   show(options: Omit<Toast, 'id'> & { id?: string }): string {
     const id = options.id || Math.random().toString(36).substr(2, 9);
@@ -85,14 +88,20 @@ class ToastService {
       ...restOptions,
     };
 
-    this.toasts.push(toast);
+    const index = this.toasts.findIndex(existing => existing.id === id);
+    if (index > -1) {
+      this.toasts[index] = toast;
+    } else {
+      this.toasts.push(toast);
+    }
+    this.clearTimer(id);
     this.notifyListeners();
 
     // Auto-dismiss if not persistent
     if (!toast.persistent && toast.duration && toast.duration > 0) {
-      setTimeout(() => {
+      this.timers.set(id, setTimeout(() => {
         this.dismiss(id);
-      }, toast.duration);
+      }, toast.duration));
     }
 
     return id;
@@ -102,6 +111,7 @@ class ToastService {
    * Dismiss a toast by ID
    */
   dismiss(id: string): void {
+    this.clearTimer(id);
     const index = this.toasts.findIndex(toast => toast.id === id);
     if (index > -1) {
       this.toasts.splice(index, 1);
@@ -113,10 +123,20 @@ class ToastService {
    * Dismiss all toasts
    */
   dismissAll(): void {
+    this.timers.forEach(timer => clearTimeout(timer));
+    this.timers.clear();
     this.toasts = [];
     this.notifyListeners();
   }
 
+  private clearTimer(id: string): void {
+    const timer = this.timers.get(id);
+    if (timer !== undefined) {
+      clearTimeout(timer);
+      this.timers.delete(id);
+    }
+  }
+
   /**
    * Get all current toasts
    */
@@ -128,7 +128,7 @@ class TrayNotificationManager {
         await invoke('show_tray_notification', {
           title: notification.title,
           message: notification.message || '',
-          type: notification.type,
+          notificationType: notification.type,
         });
       }
     } catch (error) {